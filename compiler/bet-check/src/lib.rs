@@ -0,0 +1,954 @@
+//! Type checker for betlang.
+//!
+//! `check` is a work in progress: it currently handles literals, variable
+//! lookups, lambdas/application and `let` (with let-polymorphism), records
+//! and field access, `match` (with exhaustiveness checking), arithmetic,
+//! comparison and logical operators, `if`, plain/weighted/conditional
+//! `bet` expressions, `sample`, `parallel`, `return`, and `do`-notation
+//! over the distribution monad. Everything else (e.g. `observe`, `infer`)
+//! falls through to `Type::Unit` until its own inference rule is added.
+
+use std::collections::{HashMap, HashSet};
+
+use bet_core::{BinOp, CompileError, CompileResult, DoStmt, Expr, Literal, MatchArm, Pattern, Substitution, Ternary, Type, UnOp};
+use indexmap::IndexMap;
+
+mod natives;
+pub use natives::native_signatures;
+
+/// A `let`-generalized type: `forall vars. ty`. Variables in `vars` are
+/// instantiated freshly at each use of the binding.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scheme {
+    pub vars: Vec<u32>,
+    pub ty: Type,
+}
+
+impl Scheme {
+    /// A scheme with no quantified variables: an ordinary monomorphic type.
+    pub fn mono(ty: Type) -> Self {
+        Scheme { vars: Vec::new(), ty }
+    }
+}
+
+pub type TypeEnv = HashMap<String, Scheme>;
+
+/// Mints fresh, never-before-seen type variable ids.
+#[derive(Debug, Default)]
+struct TyVarGen(u32);
+
+impl TyVarGen {
+    fn fresh(&mut self) -> Type {
+        let id = self.0;
+        self.0 += 1;
+        Type::Var(id)
+    }
+}
+
+struct Checker {
+    subst: Substitution,
+    tygen: TyVarGen,
+}
+
+impl Checker {
+    fn new() -> Self {
+        Checker {
+            subst: Substitution::new(),
+            tygen: TyVarGen::default(),
+        }
+    }
+
+    fn unify(&mut self, a: &Type, b: &Type) -> CompileResult<()> {
+        self.subst.unify(a, b)
+    }
+
+    /// Replaces `scheme`'s quantified variables with fresh ones.
+    fn instantiate(&mut self, scheme: &Scheme) -> Type {
+        let mapping: HashMap<u32, Type> =
+            scheme.vars.iter().map(|v| (*v, self.tygen.fresh())).collect();
+        substitute_vars(&scheme.ty, &mapping)
+    }
+
+    /// Quantifies every variable free in `ty` but not free in `env`.
+    fn generalize(&self, env: &TypeEnv, ty: &Type) -> Scheme {
+        let ty = self.subst.apply(ty);
+        let env_vars = free_vars_env(env, &self.subst);
+        let mut vars: Vec<u32> = free_vars(&ty).into_iter().filter(|v| !env_vars.contains(v)).collect();
+        vars.sort_unstable();
+        Scheme { vars, ty }
+    }
+
+    fn infer(&mut self, expr: &Expr, env: &TypeEnv) -> CompileResult<Type> {
+        match expr {
+            Expr::Literal(lit) => Ok(check_literal(lit)),
+            Expr::Var(name) => {
+                let scheme = env.get(name).cloned().or_else(|| native_signatures().remove(name));
+                let scheme = scheme
+                    .ok_or_else(|| CompileError::Type(format!("unbound variable `{name}`")))?;
+                Ok(self.instantiate(&scheme))
+            }
+            Expr::Lambda(lambda) => self.infer_lambda(&lambda.params, &lambda.body, env),
+            Expr::App(func, arg) => {
+                let func_ty = self.infer(func, env)?;
+                let arg_ty = self.infer(arg, env)?;
+                let result_ty = self.tygen.fresh();
+                self.unify(
+                    &func_ty,
+                    &Type::Fun(Box::new(arg_ty), Box::new(result_ty.clone())),
+                )?;
+                Ok(self.subst.apply(&result_ty))
+            }
+            Expr::Let(name, value, body) => {
+                let value_ty = self.infer(value, env)?;
+                let scheme = self.generalize(env, &value_ty);
+                let mut env = env.clone();
+                env.insert(name.clone(), scheme);
+                self.infer(body, &env)
+            }
+            Expr::Record(fields) => {
+                let mut field_types = IndexMap::new();
+                for (name, value) in fields {
+                    let ty = self.infer(value, env)?;
+                    field_types.insert(name.clone(), ty);
+                }
+                Ok(Type::Record(field_types))
+            }
+            // Exact record types only for now: `record` must literally
+            // contain `field`. True row polymorphism (a function accepting
+            // "any record with field x") would need a row-variable form of
+            // `Type::Record` and is left for later.
+            Expr::Field(record, field) => {
+                let record_ty = self.infer(record, env)?;
+                match self.subst.apply(&record_ty) {
+                    Type::Record(fields) => fields.get(field).cloned().ok_or_else(|| {
+                        CompileError::Type(format!("record has no field `{field}`"))
+                    }),
+                    other => Err(CompileError::Type(format!(
+                        "field access `.{field}` requires a record, found `{other}`"
+                    ))),
+                }
+            }
+            Expr::Match(scrutinee, arms) => self.infer_match(scrutinee, arms, env),
+            Expr::Sample(inner) => {
+                let ty = self.infer(inner, env)?;
+                match self.subst.apply(&ty) {
+                    Type::Dist(inner) => Ok(*inner),
+                    other => Err(CompileError::Type(format!(
+                        "`sample` requires a distribution, found `{other}`"
+                    ))),
+                }
+            }
+            Expr::Parallel(n, body) => {
+                let n_ty = self.infer(n, env)?;
+                self.unify(&n_ty, &Type::Int)?;
+                let body_ty = self.infer(body, env)?;
+                match self.subst.apply(&body_ty) {
+                    Type::Dist(inner) => Ok(Type::List(inner)),
+                    other => Err(CompileError::Type(format!(
+                        "`parallel` requires a distribution body, found `{other}`"
+                    ))),
+                }
+            }
+            Expr::Bet(alts) => self.infer_bet(alts, env),
+            Expr::Do(do_expr) => self.infer_do(&do_expr.stmts, env),
+            Expr::Return(inner) => Ok(Type::Dist(Box::new(self.infer(inner, env)?))),
+            Expr::BinOp(op, lhs, rhs) => self.infer_binop(*op, lhs, rhs, env),
+            Expr::UnOp(op, inner) => self.infer_unop(*op, inner, env),
+            Expr::If(cond, if_true, if_false) => {
+                let cond_ty = self.infer(cond, env)?;
+                self.unify(&cond_ty, &Type::Bool)?;
+                let true_ty = self.infer(if_true, env)?;
+                let false_ty = self.infer(if_false, env)?;
+                self.unify(&true_ty, &false_ty)?;
+                Ok(self.subst.apply(&true_ty))
+            }
+            Expr::WeightedBet(alts) => self.infer_weighted_bet(alts, env),
+            Expr::ConditionalBet { cond, if_true, if_false } => {
+                let cond_ty = self.infer(cond, env)?;
+                self.unify(&cond_ty, &Type::Bool)?;
+                let true_ty = self.infer(if_true, env)?;
+                let false_ty = self.infer(if_false, env)?;
+                promote_alt_types(&[true_ty, false_ty], "conditional bet branches")
+            }
+            _ => Ok(Type::Unit),
+        }
+    }
+
+    /// Arithmetic operators require both operands to unify to the same
+    /// numeric type (`Int` or `Float`) and return that type; comparisons
+    /// require the same but always return `Bool`; `And`/`Or` require both
+    /// operands to unify with `Bool` (matching [`bet_eval`]'s Kleene
+    /// three-valued logic only at the value level, not the type level --
+    /// `Ternary` has no dedicated operator typing yet).
+    fn infer_binop(&mut self, op: BinOp, lhs: &Expr, rhs: &Expr, env: &TypeEnv) -> CompileResult<Type> {
+        let lhs_ty = self.infer(lhs, env)?;
+        let rhs_ty = self.infer(rhs, env)?;
+        self.unify(&lhs_ty, &rhs_ty)?;
+        let operand_ty = self.subst.apply(&lhs_ty);
+
+        match op {
+            BinOp::Add | BinOp::Sub | BinOp::Mul | BinOp::Div | BinOp::Mod => match operand_ty {
+                Type::Int | Type::Float => Ok(operand_ty),
+                other => Err(CompileError::Type(format!(
+                    "{op:?} requires numeric operands, found `{other}`"
+                ))),
+            },
+            BinOp::Lt | BinOp::Le | BinOp::Gt | BinOp::Ge => match operand_ty {
+                Type::Int | Type::Float => Ok(Type::Bool),
+                other => Err(CompileError::Type(format!(
+                    "{op:?} requires numeric operands, found `{other}`"
+                ))),
+            },
+            BinOp::Eq | BinOp::Neq => Ok(Type::Bool),
+            BinOp::And | BinOp::Or => {
+                self.unify(&operand_ty, &Type::Bool)?;
+                Ok(Type::Bool)
+            }
+        }
+    }
+
+    fn infer_unop(&mut self, op: UnOp, inner: &Expr, env: &TypeEnv) -> CompileResult<Type> {
+        let inner_ty = self.infer(inner, env)?;
+        let inner_ty = self.subst.apply(&inner_ty);
+        match op {
+            UnOp::Neg => match inner_ty {
+                Type::Int | Type::Float => Ok(inner_ty),
+                other => Err(CompileError::Type(format!("unary `-` requires a numeric operand, found `{other}`"))),
+            },
+            UnOp::Not => {
+                self.unify(&inner_ty, &Type::Bool)?;
+                Ok(Type::Bool)
+            }
+        }
+    }
+
+    fn infer_lambda(&mut self, params: &[String], body: &Expr, env: &TypeEnv) -> CompileResult<Type> {
+        match params.split_first() {
+            None => self.infer(body, env),
+            Some((first, rest)) => {
+                let param_ty = self.tygen.fresh();
+                let mut env = env.clone();
+                env.insert(first.clone(), Scheme::mono(param_ty.clone()));
+                let rest_ty = if rest.is_empty() {
+                    self.infer(body, &env)?
+                } else {
+                    self.infer_lambda(rest, body, &env)?
+                };
+                Ok(Type::Fun(Box::new(self.subst.apply(&param_ty)), Box::new(rest_ty)))
+            }
+        }
+    }
+
+    fn infer_match(&mut self, scrutinee: &Expr, arms: &[MatchArm], env: &TypeEnv) -> CompileResult<Type> {
+        let scrutinee_ty = self.infer(scrutinee, env)?;
+        let scrutinee_ty = self.subst.apply(&scrutinee_ty);
+        check_exhaustiveness(&scrutinee_ty, arms)?;
+
+        let mut result: Option<Type> = None;
+        for arm in arms {
+            let mut arm_env = env.clone();
+            if let Pattern::Var(name) = &arm.pattern {
+                arm_env.insert(name.clone(), Scheme::mono(scrutinee_ty.clone()));
+            }
+            let body_ty = self.infer(&arm.body, &arm_env)?;
+            match result {
+                None => result = Some(body_ty),
+                Some(t) => {
+                    self.unify(&t, &body_ty)?;
+                    result = Some(self.subst.apply(&t));
+                }
+            }
+        }
+        let result = result.ok_or_else(|| CompileError::Type("`match` must have at least one arm".into()))?;
+        Ok(self.subst.apply(&result))
+    }
+
+    /// Every alternative of a `bet` must have the same type, except that
+    /// `Int` and `Float` alternatives may mix: the whole bet then promotes
+    /// to `Float`, mirroring `bet-eval`'s numeric `promote` at runtime.
+    fn infer_bet(&mut self, alts: &[Expr], env: &TypeEnv) -> CompileResult<Type> {
+        let mut alt_types = Vec::with_capacity(alts.len());
+        for alt in alts {
+            alt_types.push(self.infer(alt, env)?);
+        }
+        promote_alt_types(&alt_types, "bet alternatives")
+    }
+
+    /// Like [`Checker::infer_bet`], but each alternative carries its own
+    /// weight expression, which must itself be numeric (a raw count or a
+    /// probability works equally well, so either `Int` or `Float` is
+    /// accepted rather than requiring `Float` specifically).
+    fn infer_weighted_bet(&mut self, alts: &[(Expr, Expr)], env: &TypeEnv) -> CompileResult<Type> {
+        let mut alt_types = Vec::with_capacity(alts.len());
+        for (value, weight) in alts {
+            alt_types.push(self.infer(value, env)?);
+            match self.infer(weight, env)? {
+                Type::Int | Type::Float => {}
+                other => {
+                    return Err(CompileError::Type(format!(
+                        "bet weight must be numeric, found `{other}`"
+                    )))
+                }
+            }
+        }
+        promote_alt_types(&alt_types, "weighted bet alternatives")
+    }
+
+    /// Type-checks a `do` block as a sequence of actions in the `Dist`
+    /// monad: each `x <- e` requires `e : Dist τ` and binds `x : τ`; the
+    /// block's type is whatever its final statement produces, which must
+    /// itself be a `Dist`.
+    fn infer_do(&mut self, stmts: &[DoStmt], env: &TypeEnv) -> CompileResult<Type> {
+        if stmts.is_empty() {
+            return Err(CompileError::Type("`do` block must not be empty".into()));
+        }
+        let mut env = env.clone();
+        let mut result = Type::Unit;
+        for (i, stmt) in stmts.iter().enumerate() {
+            let is_last = i == stmts.len() - 1;
+            match stmt {
+                DoStmt::Bind(name, e) => {
+                    let ty = self.infer(e, &env)?;
+                    let inner = match ty {
+                        Type::Dist(inner) => *inner,
+                        other => {
+                            return Err(CompileError::Type(format!(
+                                "`{name} <- e` requires `e : Dist _`, found `{other}`"
+                            )))
+                        }
+                    };
+                    if is_last {
+                        return Err(CompileError::Type(
+                            "a `do` block cannot end in a bind; end with `return` or a `Dist` expression".into(),
+                        ));
+                    }
+                    env.insert(name.clone(), Scheme::mono(inner));
+                }
+                DoStmt::Let(name, e) => {
+                    let ty = self.infer(e, &env)?;
+                    let scheme = self.generalize(&env, &ty);
+                    env.insert(name.clone(), scheme);
+                }
+                DoStmt::Expr(e) => {
+                    let ty = self.infer(e, &env)?;
+                    if is_last {
+                        result = ty;
+                    }
+                }
+            }
+        }
+        match &result {
+            Type::Dist(_) => Ok(result),
+            other => Err(CompileError::Type(format!(
+                "a `do` block must end in a `Dist` expression, found `{other}`"
+            ))),
+        }
+    }
+}
+
+/// Every literal value a type with a known-finite set of inhabitants can
+/// take, or `None` if `scrutinee_ty` has no such enumeration (yet) -- e.g.
+/// `Int`, `Float`, and `Str` are unbounded, and `List`/`Record` aren't
+/// literal-shaped at all.
+fn finite_literals(scrutinee_ty: &Type) -> Option<Vec<Literal>> {
+    match scrutinee_ty {
+        Type::Unit => Some(vec![Literal::Unit]),
+        Type::Bool => Some(vec![Literal::Bool(true), Literal::Bool(false)]),
+        Type::Ternary => Some(vec![
+            Literal::Ternary(Ternary::True),
+            Literal::Ternary(Ternary::False),
+            Literal::Ternary(Ternary::Unknown),
+        ]),
+        _ => None,
+    }
+}
+
+fn literal_label(lit: &Literal) -> String {
+    match lit {
+        Literal::Unit => "Unit".to_string(),
+        Literal::Bool(b) => b.to_string(),
+        Literal::Ternary(Ternary::True) => "True".to_string(),
+        Literal::Ternary(Ternary::False) => "False".to_string(),
+        Literal::Ternary(Ternary::Unknown) => "Unknown".to_string(),
+        Literal::Int(i) => i.to_string(),
+        Literal::Float(f) => f.to_string(),
+        Literal::Str(s) => format!("{s:?}"),
+    }
+}
+
+/// Checks a `match` over a known finite scrutinee type (`Unit`, `Bool`,
+/// `Ternary`) for coverage, and flags arms that can never run because an
+/// earlier irrefutable pattern (a wildcard or bare variable, unguarded)
+/// already matches everything.
+fn check_exhaustiveness(scrutinee_ty: &Type, arms: &[MatchArm]) -> CompileResult<()> {
+    let mut seen_catchall = false;
+    for arm in arms {
+        if seen_catchall {
+            return Err(CompileError::Type(
+                "unreachable match arm: a previous wildcard already covers every case".into(),
+            ));
+        }
+        let is_catchall =
+            matches!(arm.pattern, Pattern::Wildcard | Pattern::Var(_)) && arm.guard.is_none();
+        if is_catchall {
+            seen_catchall = true;
+        }
+    }
+    if seen_catchall {
+        return Ok(());
+    }
+
+    // Other scrutinee types don't have a known finite set of constructors
+    // (yet), so there is nothing to check.
+    let Some(mut missing) = finite_literals(scrutinee_ty) else {
+        return Ok(());
+    };
+    for arm in arms {
+        if let Pattern::Literal(lit) = &arm.pattern {
+            missing.retain(|case| case != lit);
+        }
+    }
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        let missing = missing.iter().map(literal_label).collect::<Vec<_>>().join(", ");
+        Err(CompileError::Type(format!("non-exhaustive match on {scrutinee_ty}: missing {missing}")))
+    }
+}
+
+/// Merges a list of already-inferred alternative types into one, requiring
+/// them all to match except that `Int` and `Float` may mix and promote to
+/// `Float` -- the rule shared by `bet`, `bet @ weight`, and the two arms of
+/// a conditional bet. `label` names the caller for the error message.
+fn promote_alt_types(types: &[Type], label: &str) -> CompileResult<Type> {
+    let Some((first, rest)) = types.split_first() else {
+        return Err(CompileError::Type(format!("{label} must have at least one alternative")));
+    };
+    let mut result = first.clone();
+    for ty in rest {
+        result = match (&result, ty) {
+            (a, b) if a == b => result,
+            (Type::Int, Type::Float) | (Type::Float, Type::Int) => Type::Float,
+            (a, b) => return Err(CompileError::Type(format!("{label} have mismatched types: {a} vs {b}"))),
+        };
+    }
+    Ok(result)
+}
+
+fn check_literal(lit: &Literal) -> Type {
+    match lit {
+        Literal::Unit => Type::Unit,
+        Literal::Bool(_) => Type::Bool,
+        Literal::Ternary(_) => Type::Ternary,
+        Literal::Int(_) => Type::Int,
+        Literal::Float(_) => Type::Float,
+        Literal::Str(_) => Type::Str,
+    }
+}
+
+fn substitute_vars(ty: &Type, mapping: &HashMap<u32, Type>) -> Type {
+    match ty {
+        Type::Var(id) => mapping.get(id).cloned().unwrap_or_else(|| ty.clone()),
+        Type::List(t) => Type::List(Box::new(substitute_vars(t, mapping))),
+        Type::Dist(t) => Type::Dist(Box::new(substitute_vars(t, mapping))),
+        Type::Tuple(ts) => Type::Tuple(ts.iter().map(|t| substitute_vars(t, mapping)).collect()),
+        Type::Fun(a, b) => Type::Fun(
+            Box::new(substitute_vars(a, mapping)),
+            Box::new(substitute_vars(b, mapping)),
+        ),
+        Type::Record(fields) => Type::Record(
+            fields
+                .iter()
+                .map(|(k, t)| (k.clone(), substitute_vars(t, mapping)))
+                .collect(),
+        ),
+        Type::Unit | Type::Bool | Type::Ternary | Type::Int | Type::Float | Type::Str => ty.clone(),
+    }
+}
+
+fn free_vars(ty: &Type) -> HashSet<u32> {
+    let mut vars = HashSet::new();
+    collect_free_vars(ty, &mut vars);
+    vars
+}
+
+fn collect_free_vars(ty: &Type, vars: &mut HashSet<u32>) {
+    match ty {
+        Type::Var(id) => {
+            vars.insert(*id);
+        }
+        Type::List(t) | Type::Dist(t) => collect_free_vars(t, vars),
+        Type::Tuple(ts) => ts.iter().for_each(|t| collect_free_vars(t, vars)),
+        Type::Fun(a, b) => {
+            collect_free_vars(a, vars);
+            collect_free_vars(b, vars);
+        }
+        Type::Record(fields) => fields.values().for_each(|t| collect_free_vars(t, vars)),
+        Type::Unit | Type::Bool | Type::Ternary | Type::Int | Type::Float | Type::Str => {}
+    }
+}
+
+fn free_vars_env(env: &TypeEnv, subst: &Substitution) -> HashSet<u32> {
+    let mut vars = HashSet::new();
+    for scheme in env.values() {
+        let applied = subst.apply(&scheme.ty);
+        for v in free_vars(&applied) {
+            if !scheme.vars.contains(&v) {
+                vars.insert(v);
+            }
+        }
+    }
+    vars
+}
+
+/// Infers the type of `expr` under `env`, returning the fully-resolved
+/// type (with any leftover unification variables substituted away).
+pub fn check(expr: &Expr, env: &TypeEnv) -> CompileResult<Type> {
+    let mut checker = Checker::new();
+    let ty = checker.infer(expr, env)?;
+    Ok(checker.subst.apply(&ty))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bet_core::{BinOp, DoExpr, LambdaExpr};
+
+    fn normal_env() -> TypeEnv {
+        let mut env = TypeEnv::new();
+        env.insert(
+            "normal".into(),
+            Scheme::mono(Type::Fun(
+                Box::new(Type::Float),
+                Box::new(Type::Fun(
+                    Box::new(Type::Float),
+                    Box::new(Type::Dist(Box::new(Type::Float))),
+                )),
+            )),
+        );
+        env
+    }
+
+    #[test]
+    fn do_block_returns_dist_float() {
+        // do { x <- sampled_normal; return x }
+        let env = {
+            let mut env = normal_env();
+            env.insert(
+                "sampled_normal".into(),
+                Scheme::mono(Type::Dist(Box::new(Type::Float))),
+            );
+            env
+        };
+        let do_expr = Expr::Do(DoExpr {
+            stmts: vec![
+                DoStmt::Bind("x".into(), Expr::Var("sampled_normal".into())),
+                DoStmt::Expr(Expr::Return(Box::new(Expr::Var("x".into())))),
+            ],
+        });
+        assert_eq!(check(&do_expr, &env), Ok(Type::Dist(Box::new(Type::Float))));
+    }
+
+    #[test]
+    fn do_block_rejects_non_distribution_bind() {
+        let env = TypeEnv::new();
+        let do_expr = Expr::Do(DoExpr {
+            stmts: vec![
+                DoStmt::Bind("x".into(), Expr::Literal(Literal::Int(1))),
+                DoStmt::Expr(Expr::Return(Box::new(Expr::Var("x".into())))),
+            ],
+        });
+        assert!(check(&do_expr, &env).is_err());
+    }
+
+    #[test]
+    fn bet_requires_matching_alternatives() {
+        let env = TypeEnv::new();
+        let bet = Expr::Bet(vec![
+            Expr::Literal(Literal::Int(1)),
+            Expr::Literal(Literal::Int(2)),
+        ]);
+        assert_eq!(check(&bet, &env), Ok(Type::Int));
+
+        let bad = Expr::Observe(
+            Box::new(Expr::Literal(Literal::Unit)),
+            Box::new(Expr::Literal(Literal::Unit)),
+        );
+        // Observe isn't checked yet, so it falls through to Unit -- make
+        // sure mismatched bet alternatives are still caught against that.
+        let bet = Expr::Bet(vec![Expr::Literal(Literal::Int(1)), bad]);
+        assert!(check(&bet, &env).is_err());
+    }
+
+    #[test]
+    fn bet_promotes_mixed_int_and_float_alternatives_to_float() {
+        let env = TypeEnv::new();
+        let bet = Expr::Bet(vec![
+            Expr::Literal(Literal::Int(1)),
+            Expr::Literal(Literal::Float(2.5)),
+            Expr::Literal(Literal::Int(3)),
+        ]);
+        assert_eq!(check(&bet, &env), Ok(Type::Float));
+    }
+
+    #[test]
+    fn weighted_bet_requires_numeric_weights_and_matching_alternatives() {
+        let env = TypeEnv::new();
+        let bet = Expr::WeightedBet(vec![
+            (Expr::Literal(Literal::Int(1)), Expr::Literal(Literal::Float(0.25))),
+            (Expr::Literal(Literal::Int(2)), Expr::Literal(Literal::Float(0.75))),
+        ]);
+        assert_eq!(check(&bet, &env), Ok(Type::Int));
+
+        let bad_weight = Expr::WeightedBet(vec![(
+            Expr::Literal(Literal::Int(1)),
+            Expr::Literal(Literal::Str("heavy".into())),
+        )]);
+        assert!(check(&bad_weight, &env).is_err());
+
+        let mismatched = Expr::WeightedBet(vec![
+            (Expr::Literal(Literal::Int(1)), Expr::Literal(Literal::Int(1))),
+            (Expr::Literal(Literal::Str("a".into())), Expr::Literal(Literal::Int(1))),
+        ]);
+        assert!(check(&mismatched, &env).is_err());
+    }
+
+    #[test]
+    fn conditional_bet_requires_bool_condition_and_allows_numeric_promotion() {
+        let env = TypeEnv::new();
+        let bet = Expr::ConditionalBet {
+            cond: Box::new(Expr::Literal(Literal::Bool(true))),
+            if_true: Box::new(Expr::Literal(Literal::Int(1))),
+            if_false: Box::new(Expr::Literal(Literal::Float(2.0))),
+        };
+        assert_eq!(check(&bet, &env), Ok(Type::Float));
+
+        let bad_cond = Expr::ConditionalBet {
+            cond: Box::new(Expr::Literal(Literal::Int(1))),
+            if_true: Box::new(Expr::Literal(Literal::Int(1))),
+            if_false: Box::new(Expr::Literal(Literal::Int(2))),
+        };
+        assert!(check(&bad_cond, &env).is_err());
+    }
+
+    #[test]
+    fn arithmetic_requires_matching_numeric_operands() {
+        let ok = Expr::BinOp(
+            BinOp::Add,
+            Box::new(Expr::Literal(Literal::Int(1))),
+            Box::new(Expr::Literal(Literal::Int(2))),
+        );
+        assert_eq!(check(&ok, &TypeEnv::new()), Ok(Type::Int));
+
+        let mismatched = Expr::BinOp(
+            BinOp::Add,
+            Box::new(Expr::Literal(Literal::Int(1))),
+            Box::new(Expr::Literal(Literal::Float(2.0))),
+        );
+        assert!(check(&mismatched, &TypeEnv::new()).is_err());
+
+        let non_numeric = Expr::BinOp(
+            BinOp::Add,
+            Box::new(Expr::Literal(Literal::Str("a".into()))),
+            Box::new(Expr::Literal(Literal::Str("b".into()))),
+        );
+        assert!(check(&non_numeric, &TypeEnv::new()).is_err());
+    }
+
+    #[test]
+    fn comparisons_return_bool() {
+        let e = Expr::BinOp(
+            BinOp::Lt,
+            Box::new(Expr::Literal(Literal::Int(1))),
+            Box::new(Expr::Literal(Literal::Int(2))),
+        );
+        assert_eq!(check(&e, &TypeEnv::new()), Ok(Type::Bool));
+    }
+
+    #[test]
+    fn logical_operators_require_bool_operands() {
+        let e = Expr::BinOp(
+            BinOp::And,
+            Box::new(Expr::Literal(Literal::Bool(true))),
+            Box::new(Expr::Literal(Literal::Bool(false))),
+        );
+        assert_eq!(check(&e, &TypeEnv::new()), Ok(Type::Bool));
+
+        let bad = Expr::BinOp(
+            BinOp::And,
+            Box::new(Expr::Literal(Literal::Bool(true))),
+            Box::new(Expr::Literal(Literal::Int(1))),
+        );
+        assert!(check(&bad, &TypeEnv::new()).is_err());
+    }
+
+    #[test]
+    fn unary_neg_and_not() {
+        let neg = Expr::UnOp(UnOp::Neg, Box::new(Expr::Literal(Literal::Float(1.5))));
+        assert_eq!(check(&neg, &TypeEnv::new()), Ok(Type::Float));
+
+        let not = Expr::UnOp(UnOp::Not, Box::new(Expr::Literal(Literal::Bool(true))));
+        assert_eq!(check(&not, &TypeEnv::new()), Ok(Type::Bool));
+
+        let bad = Expr::UnOp(UnOp::Not, Box::new(Expr::Literal(Literal::Int(1))));
+        assert!(check(&bad, &TypeEnv::new()).is_err());
+    }
+
+    #[test]
+    fn if_requires_bool_condition_and_matching_branches() {
+        let e = Expr::If(
+            Box::new(Expr::Literal(Literal::Bool(true))),
+            Box::new(Expr::Literal(Literal::Int(1))),
+            Box::new(Expr::Literal(Literal::Int(2))),
+        );
+        assert_eq!(check(&e, &TypeEnv::new()), Ok(Type::Int));
+
+        let non_bool_cond = Expr::If(
+            Box::new(Expr::Literal(Literal::Int(1))),
+            Box::new(Expr::Literal(Literal::Int(1))),
+            Box::new(Expr::Literal(Literal::Int(2))),
+        );
+        assert!(check(&non_bool_cond, &TypeEnv::new()).is_err());
+
+        let mismatched_branches = Expr::If(
+            Box::new(Expr::Literal(Literal::Bool(true))),
+            Box::new(Expr::Literal(Literal::Int(1))),
+            Box::new(Expr::Literal(Literal::Str("a".into()))),
+        );
+        assert!(check(&mismatched_branches, &TypeEnv::new()).is_err());
+    }
+
+    #[test]
+    fn record_field_access() {
+        let record = Expr::Record(vec![
+            ("x".into(), Expr::Literal(Literal::Int(1))),
+            ("y".into(), Expr::Literal(Literal::Int(2))),
+        ]);
+        let x = Expr::Field(Box::new(record.clone()), "x".into());
+        assert_eq!(check(&x, &TypeEnv::new()), Ok(Type::Int));
+
+        let z = Expr::Field(Box::new(record), "z".into());
+        assert!(check(&z, &TypeEnv::new()).is_err());
+    }
+
+    #[test]
+    fn record_type_preserves_declaration_order() {
+        let record = Expr::Record(vec![
+            ("z".into(), Expr::Literal(Literal::Int(1))),
+            ("a".into(), Expr::Literal(Literal::Bool(true))),
+        ]);
+        let ty = check(&record, &TypeEnv::new()).unwrap();
+        assert_eq!(ty.to_string(), "{z: Int, a: Bool}");
+    }
+
+    #[test]
+    fn if_branches_returning_records_with_an_unresolved_field_var_unify() {
+        // if true then (fun y -> {x: y}) else (fun z -> {x: 1})
+        let lambda_var_field = Expr::Lambda(LambdaExpr {
+            params: vec!["y".into()],
+            body: Box::new(Expr::Record(vec![("x".into(), Expr::Var("y".into()))])),
+        });
+        let lambda_int_field = Expr::Lambda(LambdaExpr {
+            params: vec!["z".into()],
+            body: Box::new(Expr::Record(vec![("x".into(), Expr::Literal(Literal::Int(1)))])),
+        });
+        let e = Expr::If(
+            Box::new(Expr::Literal(Literal::Bool(true))),
+            Box::new(lambda_var_field),
+            Box::new(lambda_int_field),
+        );
+        let ty = check(&e, &TypeEnv::new()).expect("branches should unify");
+        match ty {
+            Type::Fun(_, ret) => assert_eq!(*ret, Type::Record(IndexMap::from([("x".to_string(), Type::Int)]))),
+            other => panic!("expected a function type, found {other}"),
+        }
+    }
+
+    fn dist_float_fn() -> Scheme {
+        Scheme::mono(Type::Fun(
+            Box::new(Type::Float),
+            Box::new(Type::Fun(
+                Box::new(Type::Float),
+                Box::new(Type::Dist(Box::new(Type::Float))),
+            )),
+        ))
+    }
+
+    #[test]
+    fn native_registry_types_normal_call() {
+        let e = Expr::App(
+            Box::new(Expr::App(
+                Box::new(Expr::Var("normal".into())),
+                Box::new(Expr::Literal(Literal::Float(0.0))),
+            )),
+            Box::new(Expr::Literal(Literal::Float(1.0))),
+        );
+        assert_eq!(check(&e, &TypeEnv::new()), Ok(Type::Dist(Box::new(Type::Float))));
+    }
+
+    #[test]
+    fn sample_unwraps_dist() {
+        let mut env = TypeEnv::new();
+        env.insert("uniform".into(), dist_float_fn());
+        let e = Expr::Sample(Box::new(Expr::App(
+            Box::new(Expr::App(
+                Box::new(Expr::Var("uniform".into())),
+                Box::new(Expr::Literal(Literal::Float(0.0))),
+            )),
+            Box::new(Expr::Literal(Literal::Float(1.0))),
+        )));
+        assert_eq!(check(&e, &env), Ok(Type::Float));
+    }
+
+    #[test]
+    fn sample_from_the_registered_dirichlet_native_is_a_list_of_floats() {
+        let mut env = TypeEnv::new();
+        env.insert("alphas".into(), Scheme::mono(Type::List(Box::new(Type::Float))));
+        let e = Expr::Sample(Box::new(Expr::App(
+            Box::new(Expr::Var("dirichlet".into())),
+            Box::new(Expr::Var("alphas".into())),
+        )));
+        assert_eq!(check(&e, &env), Ok(Type::List(Box::new(Type::Float))));
+
+        let bad = Expr::Sample(Box::new(Expr::App(
+            Box::new(Expr::Var("dirichlet".into())),
+            Box::new(Expr::Literal(Literal::Float(1.0))),
+        )));
+        // `dirichlet` takes a `List Float`, not a bare `Float` -- this must
+        // fail to unify rather than silently falling back to `Unit`.
+        assert!(check(&bad, &TypeEnv::new()).is_err());
+    }
+
+    #[test]
+    fn do_block_bind_surfaces_a_type_error_from_the_registered_multinomial_native() {
+        let do_expr = Expr::Do(DoExpr {
+            stmts: vec![
+                DoStmt::Bind(
+                    "counts".into(),
+                    Expr::App(
+                        Box::new(Expr::App(
+                            Box::new(Expr::Var("multinomial".into())),
+                            Box::new(Expr::Literal(Literal::Int(10))),
+                        )),
+                        Box::new(Expr::Literal(Literal::Str("not a list".into()))),
+                    ),
+                ),
+                DoStmt::Expr(Expr::Return(Box::new(Expr::Var("counts".into())))),
+            ],
+        });
+        // Same idea via `do`-notation: a type error in the bound
+        // expression must surface, not get swallowed.
+        assert!(check(&do_expr, &TypeEnv::new()).is_err());
+    }
+
+    #[test]
+    fn parallel_produces_list_of_samples() {
+        let mut env = TypeEnv::new();
+        env.insert("normal".into(), dist_float_fn());
+        let e = Expr::Parallel(
+            Box::new(Expr::Literal(Literal::Int(100))),
+            Box::new(Expr::App(
+                Box::new(Expr::App(
+                    Box::new(Expr::Var("normal".into())),
+                    Box::new(Expr::Literal(Literal::Float(0.0))),
+                )),
+                Box::new(Expr::Literal(Literal::Float(1.0))),
+            )),
+        );
+        assert_eq!(check(&e, &env), Ok(Type::List(Box::new(Type::Float))));
+    }
+
+    fn ternary_arm(t: Ternary, body: i64) -> MatchArm {
+        MatchArm {
+            pattern: Pattern::Literal(Literal::Ternary(t)),
+            guard: None,
+            body: Expr::Literal(Literal::Int(body)),
+        }
+    }
+
+    #[test]
+    fn ternary_match_missing_unknown_is_flagged() {
+        let scrutinee = Box::new(Expr::Literal(Literal::Ternary(Ternary::True)));
+        let arms = vec![ternary_arm(Ternary::True, 1), ternary_arm(Ternary::False, 0)];
+        let expr = Expr::Match(scrutinee, arms);
+        assert!(check(&expr, &TypeEnv::new()).is_err());
+    }
+
+    #[test]
+    fn ternary_match_covering_all_cases_passes() {
+        let scrutinee = Box::new(Expr::Literal(Literal::Ternary(Ternary::True)));
+        let arms = vec![
+            ternary_arm(Ternary::True, 1),
+            ternary_arm(Ternary::False, 0),
+            ternary_arm(Ternary::Unknown, -1),
+        ];
+        let expr = Expr::Match(scrutinee, arms);
+        assert_eq!(check(&expr, &TypeEnv::new()), Ok(Type::Int));
+    }
+
+    #[test]
+    fn unit_match_requires_the_single_unit_arm() {
+        let scrutinee = Box::new(Expr::Literal(Literal::Unit));
+        let no_arms = Expr::Match(scrutinee.clone(), vec![]);
+        assert!(check(&no_arms, &TypeEnv::new()).is_err());
+
+        let covered = Expr::Match(
+            scrutinee,
+            vec![MatchArm {
+                pattern: Pattern::Literal(Literal::Unit),
+                guard: None,
+                body: Expr::Literal(Literal::Int(0)),
+            }],
+        );
+        assert_eq!(check(&covered, &TypeEnv::new()), Ok(Type::Int));
+    }
+
+    #[test]
+    fn polymorphic_identity_used_at_two_types() {
+        // let id = fun x -> x in id 1
+        let id = Expr::Lambda(LambdaExpr {
+            params: vec!["x".into()],
+            body: Box::new(Expr::Var("x".into())),
+        });
+        let body_int = Expr::App(Box::new(Expr::Var("id".into())), Box::new(Expr::Literal(Literal::Int(1))));
+        let let_int = Expr::Let("id".into(), Box::new(id.clone()), Box::new(body_int));
+        assert_eq!(check(&let_int, &TypeEnv::new()), Ok(Type::Int));
+
+        let body_str = Expr::App(
+            Box::new(Expr::Var("id".into())),
+            Box::new(Expr::Literal(Literal::Str("a".into()))),
+        );
+        let let_str = Expr::Let("id".into(), Box::new(id), Box::new(body_str));
+        assert_eq!(check(&let_str, &TypeEnv::new()), Ok(Type::Str));
+    }
+
+    #[test]
+    fn match_arms_returning_the_same_polymorphic_shape_through_different_type_variables_unify() {
+        // match true { true -> (fun x -> x), false -> (fun y -> y) }
+        let identity = |param: &str| {
+            Expr::Lambda(LambdaExpr {
+                params: vec![param.into()],
+                body: Box::new(Expr::Var(param.into())),
+            })
+        };
+        let scrutinee = Box::new(Expr::Literal(Literal::Bool(true)));
+        let arms = vec![
+            MatchArm {
+                pattern: Pattern::Literal(Literal::Bool(true)),
+                guard: None,
+                body: identity("x"),
+            },
+            MatchArm {
+                pattern: Pattern::Literal(Literal::Bool(false)),
+                guard: None,
+                body: identity("y"),
+            },
+        ];
+        let expr = Expr::Match(scrutinee, arms);
+        let result = check(&expr, &TypeEnv::new()).expect("arms should unify");
+        match result {
+            Type::Fun(param, ret) => assert_eq!(param, ret),
+            other => panic!("expected a function type, found {other}"),
+        }
+    }
+}
@@ -0,0 +1,127 @@
+//! Declared types for native functions.
+//!
+//! Natives like `uniform`, `normal`, and `json_encode` are implemented in
+//! the runtime and have no betlang source to infer a type from, so they
+//! need a hand-written signature here before `bet-check` can type-check
+//! programs that call them.
+
+use std::collections::HashMap;
+
+use bet_core::Type;
+
+use crate::Scheme;
+
+/// Builds a `Fun(Float, Fun(Float, Dist(inner)))` scheme, the shape shared
+/// by every two-parameter location/scale-style distribution constructor.
+fn two_param_dist(inner: Type) -> Scheme {
+    Scheme::mono(Type::Fun(
+        Box::new(Type::Float),
+        Box::new(Type::Fun(Box::new(Type::Float), Box::new(Type::Dist(Box::new(inner))))),
+    ))
+}
+
+/// Returns the declared type scheme for every native function bet-check
+/// knows about. Consulted when a `Var` doesn't resolve in the ordinary
+/// type environment.
+pub fn native_signatures() -> HashMap<String, Scheme> {
+    let mut m = HashMap::new();
+    m.insert("normal".into(), two_param_dist(Type::Float));
+    m.insert("uniform".into(), two_param_dist(Type::Float));
+    m.insert(
+        "bernoulli".into(),
+        Scheme::mono(Type::Fun(
+            Box::new(Type::Float),
+            Box::new(Type::Dist(Box::new(Type::Bool))),
+        )),
+    );
+    m.insert(
+        "json_encode".into(),
+        Scheme {
+            vars: vec![0],
+            ty: Type::Fun(Box::new(Type::Var(0)), Box::new(Type::Str)),
+        },
+    );
+    // These three signatures (through `multinomial` below) were registered
+    // under Hyperpolymath/betlang#synth-1782, whose title asked for a `Dist`
+    // monad type with `sample`/`do` checked against it -- but `Expr::Sample`
+    // and `Expr::Do` were already typed against `Type::Dist` by earlier work
+    // (Hyperpolymath/betlang#synth-1725, #synth-1720; see `infer` in
+    // `lib.rs`). That request was already satisfied by the time this ran;
+    // registering these natives' signatures was separate, newly-identified
+    // follow-up so `sample`/`do` could actually type-check calls to them.
+    m.insert(
+        "distribution_from_histogram".into(),
+        Scheme::mono(Type::Fun(
+            Box::new(Type::List(Box::new(Type::Float))),
+            Box::new(Type::Fun(
+                Box::new(Type::List(Box::new(Type::Int))),
+                Box::new(Type::Dist(Box::new(Type::Float))),
+            )),
+        )),
+    );
+    m.insert(
+        "dirichlet".into(),
+        Scheme::mono(Type::Fun(
+            Box::new(Type::List(Box::new(Type::Float))),
+            Box::new(Type::Dist(Box::new(Type::List(Box::new(Type::Float))))),
+        )),
+    );
+    m.insert(
+        "multinomial".into(),
+        Scheme::mono(Type::Fun(
+            Box::new(Type::Int),
+            Box::new(Type::Fun(
+                Box::new(Type::List(Box::new(Type::Float))),
+                Box::new(Type::Dist(Box::new(Type::List(Box::new(Type::Int))))),
+            )),
+        )),
+    );
+    m
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normal_is_a_two_param_dist_float_constructor() {
+        let sigs = native_signatures();
+        let scheme = sigs.get("normal").expect("normal should be registered");
+        assert_eq!(
+            scheme.ty,
+            Type::Fun(
+                Box::new(Type::Float),
+                Box::new(Type::Fun(Box::new(Type::Float), Box::new(Type::Dist(Box::new(Type::Float)))))
+            )
+        );
+    }
+
+    #[test]
+    fn dirichlet_draws_a_distribution_over_a_list_of_floats() {
+        let sigs = native_signatures();
+        let scheme = sigs.get("dirichlet").expect("dirichlet should be registered");
+        assert_eq!(
+            scheme.ty,
+            Type::Fun(
+                Box::new(Type::List(Box::new(Type::Float))),
+                Box::new(Type::Dist(Box::new(Type::List(Box::new(Type::Float)))))
+            )
+        );
+    }
+
+    #[test]
+    fn multinomial_draws_a_distribution_over_a_list_of_ints() {
+        let sigs = native_signatures();
+        let scheme = sigs.get("multinomial").expect("multinomial should be registered");
+        assert_eq!(
+            scheme.ty,
+            Type::Fun(
+                Box::new(Type::Int),
+                Box::new(Type::Fun(
+                    Box::new(Type::List(Box::new(Type::Float))),
+                    Box::new(Type::Dist(Box::new(Type::List(Box::new(Type::Int)))))
+                ))
+            )
+        );
+    }
+}
@@ -0,0 +1,124 @@
+/// Three-valued logic value: the result of an uncertain proposition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ternary {
+    True,
+    False,
+    Unknown,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Literal {
+    Unit,
+    Bool(bool),
+    Ternary(Ternary),
+    Int(i64),
+    Float(f64),
+    Str(String),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+    Eq,
+    Neq,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    And,
+    Or,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnOp {
+    Neg,
+    Not,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LambdaExpr {
+    pub params: Vec<String>,
+    pub body: Box<Expr>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Pattern {
+    Wildcard,
+    Literal(Literal),
+    Var(String),
+    Tuple(Vec<Pattern>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchArm {
+    pub pattern: Pattern,
+    pub guard: Option<Expr>,
+    pub body: Expr,
+}
+
+/// One statement inside a `do { ... }` block over the distribution monad.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DoStmt {
+    /// `x <- e`: sample from the distribution `e` and bind `x`.
+    Bind(String, Expr),
+    /// `let x = e`: an ordinary, non-sampling binding.
+    Let(String, Expr),
+    /// A bare expression evaluated for effect (or as the final result).
+    Expr(Expr),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DoExpr {
+    pub stmts: Vec<DoStmt>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InferMethod {
+    MCMC,
+    SMC,
+    VI,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Literal(Literal),
+    Var(String),
+    BinOp(BinOp, Box<Expr>, Box<Expr>),
+    UnOp(UnOp, Box<Expr>),
+    If(Box<Expr>, Box<Expr>, Box<Expr>),
+    Match(Box<Expr>, Vec<MatchArm>),
+    Let(String, Box<Expr>, Box<Expr>),
+    Lambda(LambdaExpr),
+    App(Box<Expr>, Box<Expr>),
+    /// `bet { a, b, c }`: an unweighted three-way (or N-way) bet.
+    Bet(Vec<Expr>),
+    /// `bet { a @ w1, b @ w2, ... }`: a bet with explicit weights.
+    WeightedBet(Vec<(Expr, Expr)>),
+    ConditionalBet {
+        cond: Box<Expr>,
+        if_true: Box<Expr>,
+        if_false: Box<Expr>,
+    },
+    Record(Vec<(String, Expr)>),
+    Field(Box<Expr>, String),
+    /// Draw a single sample from a `Dist`.
+    Sample(Box<Expr>),
+    /// Condition the model on an observed value from a distribution.
+    Observe(Box<Expr>, Box<Expr>),
+    /// `parallel n { e }`: draw `n` independent samples of `e`.
+    Parallel(Box<Expr>, Box<Expr>),
+    Do(DoExpr),
+    /// `return e` inside a `do` block: lifts a value into the `Dist` monad.
+    Return(Box<Expr>),
+    Infer(Box<Expr>, InferMethod),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Item {
+    Let(String, Expr),
+    Expr(Expr),
+}
@@ -0,0 +1,46 @@
+use thiserror::Error;
+
+/// Errors shared across every stage of the betlang compiler and runtime.
+#[derive(Debug, Clone, Error, PartialEq)]
+pub enum CompileError {
+    #[error("parse error: {0}")]
+    Parse(String),
+    #[error("type error: {0}")]
+    Type(String),
+    #[error("could not unify types: {0}")]
+    UnificationError(String),
+    #[error("runtime error: {0}")]
+    Runtime(String),
+    #[error("I/O error: {0}")]
+    Io(String),
+}
+
+impl CompileError {
+    /// The process exit code the CLI reports for this error kind: 1 for
+    /// runtime errors, 2 for type errors, 3 for parse errors, 4 for I/O
+    /// errors. Stable across releases so scripts can branch on it.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CompileError::Runtime(_) => 1,
+            CompileError::Type(_) | CompileError::UnificationError(_) => 2,
+            CompileError::Parse(_) => 3,
+            CompileError::Io(_) => 4,
+        }
+    }
+}
+
+pub type CompileResult<T> = Result<T, CompileError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exit_codes_follow_the_documented_contract() {
+        assert_eq!(CompileError::Runtime("x".into()).exit_code(), 1);
+        assert_eq!(CompileError::Type("x".into()).exit_code(), 2);
+        assert_eq!(CompileError::UnificationError("x".into()).exit_code(), 2);
+        assert_eq!(CompileError::Parse("x".into()).exit_code(), 3);
+        assert_eq!(CompileError::Io("x".into()).exit_code(), 4);
+    }
+}
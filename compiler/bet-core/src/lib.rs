@@ -0,0 +1,14 @@
+//! `bet-core` holds the pieces shared by every stage of the betlang
+//! toolchain: the AST (`ast`), the type language (`types`), unification
+//! over that type language (`unify`), and the common error type
+//! (`error`) that parsing, checking, and evaluation all report through.
+
+pub mod ast;
+pub mod error;
+pub mod types;
+pub mod unify;
+
+pub use ast::*;
+pub use error::{CompileError, CompileResult};
+pub use types::Type;
+pub use unify::Substitution;
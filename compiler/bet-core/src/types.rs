@@ -0,0 +1,65 @@
+use std::fmt;
+
+use indexmap::IndexMap;
+
+/// The betlang type language.
+///
+/// `Var` carries a fresh unification variable id, minted by a `TyVarGen`
+/// during type checking; by the time a program is fully checked no `Var`
+/// should remain in a reported type.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Type {
+    Unit,
+    Bool,
+    Ternary,
+    Int,
+    Float,
+    Str,
+    List(Box<Type>),
+    Tuple(Vec<Type>),
+    Fun(Box<Type>, Box<Type>),
+    Dist(Box<Type>),
+    /// Field order matches the order fields were declared in, via
+    /// [`IndexMap`] -- a plain `HashMap`/`BTreeMap` would scramble or
+    /// alphabetize column order, which matters for matching a record
+    /// literal's printed shape back to the source that produced it.
+    Record(IndexMap<String, Type>),
+    Var(u32),
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::Unit => write!(f, "Unit"),
+            Type::Bool => write!(f, "Bool"),
+            Type::Ternary => write!(f, "Ternary"),
+            Type::Int => write!(f, "Int"),
+            Type::Float => write!(f, "Float"),
+            Type::Str => write!(f, "String"),
+            Type::List(t) => write!(f, "List {}", t),
+            Type::Tuple(ts) => {
+                write!(f, "(")?;
+                for (i, t) in ts.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", t)?;
+                }
+                write!(f, ")")
+            }
+            Type::Fun(a, b) => write!(f, "({} -> {})", a, b),
+            Type::Dist(t) => write!(f, "Dist {}", t),
+            Type::Record(fields) => {
+                write!(f, "{{")?;
+                for (i, (k, t)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}: {}", k, t)?;
+                }
+                write!(f, "}}")
+            }
+            Type::Var(id) => write!(f, "'t{}", id),
+        }
+    }
+}
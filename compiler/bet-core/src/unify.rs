@@ -0,0 +1,144 @@
+//! Unification over the betlang type language.
+//!
+//! Lives in `bet-core` (rather than `bet-check`, which first introduced
+//! it) so that other passes over the AST -- not just the checker -- can
+//! build and substitute types without depending on `bet-check`.
+
+use std::collections::HashMap;
+
+use crate::error::{CompileError, CompileResult};
+use crate::types::Type;
+
+/// A substitution from unification variable ids to the types they were
+/// resolved to.
+#[derive(Debug, Clone, Default)]
+pub struct Substitution(HashMap<u32, Type>);
+
+impl Substitution {
+    pub fn new() -> Self {
+        Self(HashMap::new())
+    }
+
+    /// Recursively replaces every bound variable in `ty` with its binding.
+    pub fn apply(&self, ty: &Type) -> Type {
+        match ty {
+            Type::Var(id) => match self.0.get(id) {
+                Some(bound) => self.apply(bound),
+                None => ty.clone(),
+            },
+            Type::List(t) => Type::List(Box::new(self.apply(t))),
+            Type::Tuple(ts) => Type::Tuple(ts.iter().map(|t| self.apply(t)).collect()),
+            Type::Fun(a, b) => Type::Fun(Box::new(self.apply(a)), Box::new(self.apply(b))),
+            Type::Dist(t) => Type::Dist(Box::new(self.apply(t))),
+            Type::Record(fields) => {
+                Type::Record(fields.iter().map(|(k, t)| (k.clone(), self.apply(t))).collect())
+            }
+            Type::Unit | Type::Bool | Type::Ternary | Type::Int | Type::Float | Type::Str => {
+                ty.clone()
+            }
+        }
+    }
+
+    /// Does `var` occur free inside `ty` (after following existing
+    /// bindings)? Used to reject types like `'a ~ List 'a` that would
+    /// otherwise unify into an infinite type.
+    fn occurs(&self, var: u32, ty: &Type) -> bool {
+        match self.apply(ty) {
+            Type::Var(id) => id == var,
+            Type::List(t) | Type::Dist(t) => self.occurs(var, &t),
+            Type::Tuple(ts) => ts.iter().any(|t| self.occurs(var, t)),
+            Type::Fun(a, b) => self.occurs(var, &a) || self.occurs(var, &b),
+            Type::Record(fields) => fields.values().any(|t| self.occurs(var, t)),
+            Type::Unit | Type::Bool | Type::Ternary | Type::Int | Type::Float | Type::Str => false,
+        }
+    }
+
+    fn bind(&mut self, var: u32, ty: Type) -> CompileResult<()> {
+        if let Type::Var(id) = ty {
+            if id == var {
+                return Ok(());
+            }
+        }
+        if self.occurs(var, &ty) {
+            return Err(CompileError::UnificationError(format!(
+                "occurs check failed: 't{var} occurs in {}",
+                self.apply(&ty)
+            )));
+        }
+        self.0.insert(var, ty);
+        Ok(())
+    }
+
+    /// Unifies `a` and `b`, extending `self` with any new bindings needed
+    /// to make them equal. Fails with `CompileError::UnificationError` on
+    /// mismatched constructors or a failed occurs-check.
+    pub fn unify(&mut self, a: &Type, b: &Type) -> CompileResult<()> {
+        let a = self.apply(a);
+        let b = self.apply(b);
+        match (&a, &b) {
+            (Type::Var(id), _) => self.bind(*id, b),
+            (_, Type::Var(id)) => self.bind(*id, a),
+            (Type::List(x), Type::List(y)) | (Type::Dist(x), Type::Dist(y)) => self.unify(x, y),
+            (Type::Fun(a1, a2), Type::Fun(b1, b2)) => {
+                self.unify(a1, b1)?;
+                self.unify(a2, b2)
+            }
+            (Type::Tuple(xs), Type::Tuple(ys)) if xs.len() == ys.len() => {
+                for (x, y) in xs.iter().zip(ys) {
+                    self.unify(x, y)?;
+                }
+                Ok(())
+            }
+            (Type::Record(xs), Type::Record(ys)) if xs.len() == ys.len() && xs.keys().all(|k| ys.contains_key(k)) => {
+                for (k, x) in xs {
+                    self.unify(x, &ys[k])?;
+                }
+                Ok(())
+            }
+            _ if a == b => Ok(()),
+            _ => Err(CompileError::UnificationError(format!(
+                "cannot unify `{a}` with `{b}`"
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use indexmap::IndexMap;
+
+    use super::*;
+
+    #[test]
+    fn occurs_check_rejects_infinite_type() {
+        let mut subst = Substitution::new();
+        let result = subst.unify(&Type::Var(0), &Type::List(Box::new(Type::Var(0))));
+        assert!(matches!(result, Err(CompileError::UnificationError(_))));
+    }
+
+    #[test]
+    fn unifies_list_of_var_with_list_of_int() {
+        let mut subst = Substitution::new();
+        subst
+            .unify(&Type::List(Box::new(Type::Var(0))), &Type::List(Box::new(Type::Int)))
+            .unwrap();
+        assert_eq!(subst.apply(&Type::Var(0)), Type::Int);
+    }
+
+    #[test]
+    fn unifies_records_with_the_same_fields_pairwise() {
+        let mut subst = Substitution::new();
+        let with_var = Type::Record(IndexMap::from([("x".to_string(), Type::Var(0))]));
+        let with_int = Type::Record(IndexMap::from([("x".to_string(), Type::Int)]));
+        subst.unify(&with_var, &with_int).unwrap();
+        assert_eq!(subst.apply(&Type::Var(0)), Type::Int);
+    }
+
+    #[test]
+    fn records_with_different_field_sets_fail_to_unify() {
+        let mut subst = Substitution::new();
+        let xy = Type::Record(IndexMap::from([("x".to_string(), Type::Int), ("y".to_string(), Type::Int)]));
+        let x = Type::Record(IndexMap::from([("x".to_string(), Type::Int)]));
+        assert!(matches!(subst.unify(&xy, &x), Err(CompileError::UnificationError(_))));
+    }
+}
@@ -0,0 +1,713 @@
+//! Tree-walking interpreter for betlang.
+//!
+//! `eval` is a work in progress: it currently handles literals, variable
+//! lookups, `let`, full int/float arithmetic and comparisons, equality and
+//! Kleene `and`/`or`/`not` via `Expr::BinOp`/`Expr::UnOp`, `if`, `match`,
+//! lambdas and application (closures and natives), `sample`, plain `bet`,
+//! `return`, `do`, and `observe`. Everything else (e.g. weighted and
+//! conditional bets, records, `parallel`, `infer`) falls through to
+//! `Value::Unit` until its own evaluation rule is added.
+
+use std::sync::Arc;
+
+use bet_core::{BinOp, CompileError, CompileResult, DoStmt, Expr, LambdaExpr, Literal, MatchArm, Pattern, Ternary, UnOp};
+use bet_rt::random::{categorical, point_mass, Distribution};
+use bet_rt::value::NativeFunction;
+pub use bet_rt::value::{Closure, Value, ValueEnv};
+
+/// State threaded through an [`eval`] call that isn't naturally part of the
+/// environment: currently just the log-weight `Expr::Observe` accumulates,
+/// for importance-sampling-style likelihood weighting of a model against
+/// observed data.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct EvalContext {
+    pub log_weight: f64,
+}
+
+/// The initial environment every betlang program runs under: every native
+/// function `bet-rt` exports (distributions, stats, fitting, encoding,
+/// hashing, ids, data munging, container conversions, string manipulation)
+/// plus `bet-viz`'s plot builders, bound by name as a [`Value::Native`].
+pub fn prelude() -> ValueEnv {
+    let mut env = ValueEnv::new();
+    let native_groups = [
+        bet_rt::assert::native_functions(),
+        bet_rt::collections::native_functions(),
+        bet_rt::data::native_functions(),
+        bet_rt::encoding::native_functions(),
+        bet_rt::fit::native_functions(),
+        bet_rt::hashing::native_functions(),
+        bet_rt::id::native_functions(),
+        bet_rt::json::native_functions(),
+        bet_rt::random::native_functions(),
+        bet_rt::stats::native_functions(),
+        bet_rt::strings::native_functions(),
+        bet_viz::native::native_functions(),
+    ];
+    for natives in native_groups {
+        for (name, native) in natives {
+            env.insert(name, Value::Native(Arc::new(native)));
+        }
+    }
+    env
+}
+
+fn eval_literal(lit: &Literal) -> Value {
+    match lit {
+        Literal::Unit => Value::Unit,
+        Literal::Bool(b) => Value::Bool(*b),
+        Literal::Ternary(t) => Value::Ternary(*t),
+        Literal::Int(i) => Value::Int(*i),
+        Literal::Float(x) => Value::Float(*x),
+        Literal::Str(s) => Value::String(s.clone()),
+    }
+}
+
+/// Widens `a` and `b` to a common numeric type: both `Int` stays `Int`,
+/// otherwise both become `Float`. Errors on non-numeric operands.
+fn promote(a: &Value, b: &Value) -> CompileResult<(Value, Value)> {
+    match (a, b) {
+        (Value::Int(_), Value::Int(_)) => Ok((a.clone(), b.clone())),
+        (Value::Int(x), Value::Float(_)) => Ok((Value::Float(*x as f64), b.clone())),
+        (Value::Float(_), Value::Int(y)) => Ok((a.clone(), Value::Float(*y as f64))),
+        (Value::Float(_), Value::Float(_)) => Ok((a.clone(), b.clone())),
+        (other_a, other_b) => Err(CompileError::Runtime(format!(
+            "expected numeric operands, found `{other_a}` and `{other_b}`"
+        ))),
+    }
+}
+
+fn eval_arith(op: BinOp, a: Value, b: Value) -> CompileResult<Value> {
+    let (a, b) = promote(&a, &b)?;
+    if op == BinOp::Div || op == BinOp::Mod {
+        let divisor = match &b {
+            Value::Int(i) => *i as f64,
+            Value::Float(x) => *x,
+            _ => unreachable!("promote only returns Int or Float"),
+        };
+        if divisor == 0.0 {
+            return Err(CompileError::Runtime("division by zero".into()));
+        }
+    }
+    Ok(match (a, b) {
+        (Value::Int(x), Value::Int(y)) => Value::Int(match op {
+            BinOp::Add => x + y,
+            BinOp::Sub => x - y,
+            BinOp::Mul => x * y,
+            BinOp::Div => x / y,
+            BinOp::Mod => x % y,
+            _ => unreachable!("eval_arith only called for arithmetic ops"),
+        }),
+        (Value::Float(x), Value::Float(y)) => Value::Float(match op {
+            BinOp::Add => x + y,
+            BinOp::Sub => x - y,
+            BinOp::Mul => x * y,
+            BinOp::Div => x / y,
+            BinOp::Mod => x % y,
+            _ => unreachable!("eval_arith only called for arithmetic ops"),
+        }),
+        _ => unreachable!("promote only returns matching Int/Int or Float/Float pairs"),
+    })
+}
+
+fn eval_compare(op: BinOp, a: Value, b: Value) -> CompileResult<Value> {
+    let (a, b) = promote(&a, &b)?;
+    let ordering = match (a, b) {
+        (Value::Int(x), Value::Int(y)) => x.partial_cmp(&y),
+        (Value::Float(x), Value::Float(y)) => x.partial_cmp(&y),
+        _ => unreachable!("promote only returns matching Int/Int or Float/Float pairs"),
+    };
+    let Some(ordering) = ordering else {
+        return Err(CompileError::Runtime("comparison produced no ordering (NaN?)".into()));
+    };
+    Ok(Value::Bool(match op {
+        BinOp::Lt => ordering.is_lt(),
+        BinOp::Le => ordering.is_le(),
+        BinOp::Gt => ordering.is_gt(),
+        BinOp::Ge => ordering.is_ge(),
+        _ => unreachable!("eval_compare only called for ordering ops"),
+    }))
+}
+
+fn as_ternary(v: &Value) -> Option<Ternary> {
+    match v {
+        Value::Bool(true) => Some(Ternary::True),
+        Value::Bool(false) => Some(Ternary::False),
+        Value::Ternary(t) => Some(*t),
+        _ => None,
+    }
+}
+
+/// Wraps a [`Ternary`] result back into a `Bool` if both inputs were
+/// plain booleans, or a `Ternary` if either input was a `Ternary`.
+fn ternary_result(result: Ternary, both_bool: bool) -> Value {
+    if both_bool {
+        match result {
+            Ternary::True => Value::Bool(true),
+            Ternary::False => Value::Bool(false),
+            Ternary::Unknown => unreachable!("both_bool inputs can never produce Unknown"),
+        }
+    } else {
+        Value::Ternary(result)
+    }
+}
+
+/// `and`/`or` with short-circuiting and Kleene three-valued logic: a
+/// `Bool` operand is just `Ternary::True`/`False` in disguise, so mixing
+/// `Bool` and `Ternary` operands (e.g. `true and unknown`) is well-defined
+/// and yields `Ternary::Unknown` rather than an error.
+fn eval_logical(op: BinOp, expr_env: (&Expr, &Expr), env: &ValueEnv, ctx: &mut EvalContext) -> CompileResult<Value> {
+    let (lhs, rhs) = expr_env;
+    let left = eval(lhs, env, ctx)?;
+    let left_t = as_ternary(&left)
+        .ok_or_else(|| CompileError::Runtime(format!("expected a Bool or Ternary, found `{left}`")))?;
+
+    // Short-circuit before evaluating the right-hand side.
+    match (op, left_t) {
+        (BinOp::And, Ternary::False) => return Ok(Value::Bool(false)),
+        (BinOp::Or, Ternary::True) => return Ok(Value::Bool(true)),
+        _ => {}
+    }
+
+    let right = eval(rhs, env, ctx)?;
+    let right_t = as_ternary(&right)
+        .ok_or_else(|| CompileError::Runtime(format!("expected a Bool or Ternary, found `{right}`")))?;
+    let both_bool = matches!(left, Value::Bool(_)) && matches!(right, Value::Bool(_));
+
+    let result = match op {
+        BinOp::And => match (left_t, right_t) {
+            (Ternary::False, _) | (_, Ternary::False) => Ternary::False,
+            (Ternary::Unknown, _) | (_, Ternary::Unknown) => Ternary::Unknown,
+            (Ternary::True, Ternary::True) => Ternary::True,
+        },
+        BinOp::Or => match (left_t, right_t) {
+            (Ternary::True, _) | (_, Ternary::True) => Ternary::True,
+            (Ternary::Unknown, _) | (_, Ternary::Unknown) => Ternary::Unknown,
+            (Ternary::False, Ternary::False) => Ternary::False,
+        },
+        _ => unreachable!("eval_logical only called for And/Or"),
+    };
+    Ok(ternary_result(result, both_bool))
+}
+
+/// Tries to match `value` against `pattern`, returning the variable
+/// bindings it introduces (empty for patterns that bind nothing), or
+/// `None` if `value` doesn't match.
+fn match_pattern(pattern: &Pattern, value: &Value) -> Option<Vec<(String, Value)>> {
+    match pattern {
+        Pattern::Wildcard => Some(Vec::new()),
+        Pattern::Var(name) => Some(vec![(name.clone(), value.clone())]),
+        Pattern::Literal(lit) => (eval_literal(lit) == *value).then(Vec::new),
+        Pattern::Tuple(patterns) => match value {
+            Value::Tuple(values) if values.len() == patterns.len() => {
+                let mut bindings = Vec::new();
+                for (p, v) in patterns.iter().zip(values) {
+                    bindings.extend(match_pattern(p, v)?);
+                }
+                Some(bindings)
+            }
+            _ => None,
+        },
+    }
+}
+
+/// Evaluates a `match`'s arms in order, running the body of the first one
+/// whose pattern matches `value` and whose guard (if any) is true.
+fn eval_match(value: &Value, arms: &[MatchArm], env: &ValueEnv, ctx: &mut EvalContext) -> CompileResult<Value> {
+    for arm in arms {
+        let Some(bindings) = match_pattern(&arm.pattern, value) else { continue };
+        let mut arm_env = env.clone();
+        arm_env.extend(bindings);
+        if let Some(guard) = &arm.guard {
+            match eval(guard, &arm_env, ctx)? {
+                Value::Bool(true) => {}
+                Value::Bool(false) => continue,
+                other => {
+                    return Err(CompileError::Runtime(format!(
+                        "match guard must be a Bool, found `{other}`"
+                    )))
+                }
+            }
+        }
+        return eval(&arm.body, &arm_env, ctx);
+    }
+    Err(CompileError::Runtime("no match arm matched the scrutinee".into()))
+}
+
+/// Evaluates a `do { ... }` block. If it contains no `x <- e` binds, it's
+/// just a sequence: each statement runs in turn and the last one's value is
+/// the result. If it binds at least once, the block as a whole denotes a
+/// distribution (it returns a [`Value::Dist`]) whose sampler draws a fresh
+/// realization by running every bind again via [`run_do_block`].
+fn eval_do(stmts: &[DoStmt], env: &ValueEnv, ctx: &mut EvalContext) -> CompileResult<Value> {
+    if stmts.iter().any(|stmt| matches!(stmt, DoStmt::Bind(_, _))) {
+        let stmts = stmts.to_vec();
+        let outer_env = env.clone();
+        return Ok(Value::Dist(Arc::new(Distribution {
+            name: "do".to_string(),
+            params: Vec::new(),
+            sampler: Arc::new(move |rng| run_do_block(&stmts, &outer_env, rng)),
+        })));
+    }
+
+    let mut local_env = env.clone();
+    let mut result = Value::Unit;
+    for stmt in stmts {
+        result = match stmt {
+            DoStmt::Bind(..) => unreachable!("checked above: this block has no binds"),
+            DoStmt::Let(name, expr) => {
+                let value = eval(expr, &local_env, ctx)?;
+                local_env.insert(name.clone(), value.clone());
+                value
+            }
+            DoStmt::Expr(expr) => eval(expr, &local_env, ctx)?,
+        };
+    }
+    Ok(result)
+}
+
+/// Runs one full realization of a do-block that binds at least once,
+/// sampling fresh at each `x <- e`. Used as the sampler for the
+/// [`Value::Dist`] such a do-block evaluates to, so it's infallible by
+/// construction: an evaluation error becomes a [`Value::Error`] rather than
+/// propagating, mirroring how other distributions can't fail mid-sample.
+fn run_do_block(stmts: &[DoStmt], env: &ValueEnv, rng: &mut dyn rand::RngCore) -> Value {
+    let mut ctx = EvalContext::default();
+    let mut local_env = env.clone();
+    let mut result = Value::Unit;
+    for stmt in stmts {
+        let next = match stmt {
+            DoStmt::Bind(name, expr) => match eval(expr, &local_env, &mut ctx) {
+                Ok(Value::Dist(dist)) => {
+                    local_env.insert(name.clone(), dist.sample_with(rng));
+                    Ok(Value::Unit)
+                }
+                Ok(other) => Err(CompileError::Runtime(format!(
+                    "`{name} <- ...` requires a distribution, found `{other}`"
+                ))),
+                Err(e) => Err(e),
+            },
+            DoStmt::Let(name, expr) => eval(expr, &local_env, &mut ctx).inspect(|value| {
+                local_env.insert(name.clone(), value.clone());
+            }),
+            DoStmt::Expr(expr) => eval(expr, &local_env, &mut ctx),
+        };
+        match next {
+            Ok(value) => result = value,
+            Err(e) => return Value::Error(e.to_string()),
+        }
+    }
+    result
+}
+
+/// Builds a [`Value::Closure`] from a (possibly multi-parameter) lambda,
+/// desugaring `fun a b -> body` into nested single-argument closures
+/// (`fun a -> fun b -> body`) so application can curry one argument at a
+/// time.
+fn eval_lambda(params: &[String], body: &Expr, env: &ValueEnv) -> Value {
+    let (param, rest) = params.split_first().expect("a lambda always has at least one parameter");
+    let closure_body = if rest.is_empty() {
+        body.clone()
+    } else {
+        Expr::Lambda(LambdaExpr {
+            params: rest.to_vec(),
+            body: Box::new(body.clone()),
+        })
+    };
+    Value::Closure(Arc::new(Closure {
+        param: param.clone(),
+        body: closure_body,
+        env: env.clone(),
+    }))
+}
+
+/// Applies `native` to a single argument, currying one argument at a time
+/// just like [`apply`] does for closures: an arity-N native becomes an
+/// arity-(N-1) native closing over the argument already supplied, until
+/// the last argument triggers the real call.
+fn apply_native(native: &Arc<NativeFunction>, arg: Value) -> CompileResult<Value> {
+    if native.arity == 0 {
+        return Err(CompileError::Runtime(format!("`{}` takes no arguments", native.name)));
+    }
+    if native.arity == 1 {
+        return (native.func)(&[arg]).map_err(CompileError::Runtime);
+    }
+    let inner = Arc::clone(native);
+    let curried = NativeFunction {
+        name: native.name.clone(),
+        arity: native.arity - 1,
+        func: Arc::new(move |rest: &[Value]| {
+            let mut all = Vec::with_capacity(rest.len() + 1);
+            all.push(arg.clone());
+            all.extend_from_slice(rest);
+            (inner.func)(&all)
+        }),
+    };
+    Ok(Value::Native(Arc::new(curried)))
+}
+
+/// Applies `func` to a single argument.
+fn apply(func: Value, arg: Value, ctx: &mut EvalContext) -> CompileResult<Value> {
+    match func {
+        Value::Closure(closure) => {
+            let mut env = closure.env.clone();
+            env.insert(closure.param.clone(), arg);
+            eval(&closure.body, &env, ctx)
+        }
+        Value::Native(native) => apply_native(&native, arg),
+        other => Err(CompileError::Runtime(format!("cannot apply `{other}` as a function"))),
+    }
+}
+
+/// Evaluates `expr` under `env`, threading `ctx` for state (like the
+/// `Expr::Observe` log-weight) that isn't part of the environment.
+pub fn eval(expr: &Expr, env: &ValueEnv, ctx: &mut EvalContext) -> CompileResult<Value> {
+    match expr {
+        Expr::Literal(lit) => Ok(eval_literal(lit)),
+        Expr::Var(name) => env
+            .get(name)
+            .cloned()
+            .ok_or_else(|| CompileError::Runtime(format!("unbound variable `{name}`"))),
+        Expr::Let(name, value, body) => {
+            let value = eval(value, env, ctx)?;
+            let mut env = env.clone();
+            env.insert(name.clone(), value);
+            eval(body, &env, ctx)
+        }
+        Expr::BinOp(BinOp::And, lhs, rhs) => eval_logical(BinOp::And, (lhs, rhs), env, ctx),
+        Expr::BinOp(BinOp::Or, lhs, rhs) => eval_logical(BinOp::Or, (lhs, rhs), env, ctx),
+        Expr::BinOp(BinOp::Eq, lhs, rhs) => {
+            Ok(Value::Bool(eval(lhs, env, ctx)? == eval(rhs, env, ctx)?))
+        }
+        Expr::BinOp(BinOp::Neq, lhs, rhs) => {
+            Ok(Value::Bool(eval(lhs, env, ctx)? != eval(rhs, env, ctx)?))
+        }
+        Expr::BinOp(op @ (BinOp::Lt | BinOp::Le | BinOp::Gt | BinOp::Ge), lhs, rhs) => {
+            eval_compare(*op, eval(lhs, env, ctx)?, eval(rhs, env, ctx)?)
+        }
+        Expr::BinOp(op, lhs, rhs) => eval_arith(*op, eval(lhs, env, ctx)?, eval(rhs, env, ctx)?),
+        Expr::UnOp(UnOp::Neg, inner) => match eval(inner, env, ctx)? {
+            Value::Int(i) => Ok(Value::Int(-i)),
+            Value::Float(x) => Ok(Value::Float(-x)),
+            other => Err(CompileError::Runtime(format!("`-` requires a number, found `{other}`"))),
+        },
+        Expr::UnOp(UnOp::Not, inner) => match eval(inner, env, ctx)? {
+            Value::Bool(b) => Ok(Value::Bool(!b)),
+            Value::Ternary(Ternary::True) => Ok(Value::Ternary(Ternary::False)),
+            Value::Ternary(Ternary::False) => Ok(Value::Ternary(Ternary::True)),
+            Value::Ternary(Ternary::Unknown) => Ok(Value::Ternary(Ternary::Unknown)),
+            other => Err(CompileError::Runtime(format!("`not` requires a Bool or Ternary, found `{other}`"))),
+        },
+        Expr::If(cond, then_branch, else_branch) => {
+            let cond_bool = match eval(cond, env, ctx)? {
+                Value::Bool(b) => b,
+                Value::Ternary(Ternary::True) => true,
+                Value::Ternary(Ternary::False) => false,
+                Value::Ternary(Ternary::Unknown) => {
+                    return Err(CompileError::Runtime(
+                        "`if` condition is Unknown; only True/False are defined".into(),
+                    ))
+                }
+                other => {
+                    return Err(CompileError::Runtime(format!(
+                        "`if` condition must be a Bool or Ternary, found `{other}`"
+                    )))
+                }
+            };
+            eval(if cond_bool { then_branch } else { else_branch }, env, ctx)
+        }
+        Expr::Match(scrutinee, arms) => {
+            let value = eval(scrutinee, env, ctx)?;
+            eval_match(&value, arms, env, ctx)
+        }
+        Expr::Lambda(lambda) => Ok(eval_lambda(&lambda.params, &lambda.body, env)),
+        Expr::App(func, arg) => {
+            let func = eval(func, env, ctx)?;
+            let arg = eval(arg, env, ctx)?;
+            apply(func, arg, ctx)
+        }
+        Expr::Sample(inner) => match eval(inner, env, ctx)? {
+            Value::Dist(dist) => Ok(dist.sample()),
+            other => Err(CompileError::Runtime(format!("cannot sample from `{other}`: not a distribution"))),
+        },
+        Expr::Bet(exprs) => {
+            let values = exprs
+                .iter()
+                .map(|e| eval(e, env, ctx))
+                .collect::<CompileResult<Vec<_>>>()?;
+            Ok(categorical(values))
+        }
+        Expr::Return(inner) => {
+            let value = eval(inner, env, ctx)?;
+            Ok(point_mass(value))
+        }
+        Expr::Do(do_expr) => eval_do(&do_expr.stmts, env, ctx),
+        Expr::Observe(dist, value) => {
+            let dist = eval(dist, env, ctx)?;
+            let value = eval(value, env, ctx)?;
+            let Value::Dist(dist) = dist else {
+                return Err(CompileError::Runtime(format!("cannot observe against `{dist}`: not a distribution")));
+            };
+            if let Some(log_p) = dist.log_density(&value) {
+                ctx.log_weight += log_p;
+            }
+            Ok(value)
+        }
+        _ => Ok(Value::Unit),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bet_core::DoExpr;
+
+    use super::*;
+
+    fn lit_int(i: i64) -> Box<Expr> {
+        Box::new(Expr::Literal(Literal::Int(i)))
+    }
+
+    fn lit_float(x: f64) -> Box<Expr> {
+        Box::new(Expr::Literal(Literal::Float(x)))
+    }
+
+    #[test]
+    fn adds_two_ints() {
+        let expr = Expr::BinOp(BinOp::Add, lit_int(1), lit_int(2));
+        assert_eq!(eval(&expr, &ValueEnv::new(), &mut EvalContext::default()), Ok(Value::Int(3)));
+    }
+
+    #[test]
+    fn mixed_float_int_multiplication_promotes_to_float() {
+        let expr = Expr::BinOp(BinOp::Mul, lit_float(3.0), lit_int(2));
+        assert_eq!(eval(&expr, &ValueEnv::new(), &mut EvalContext::default()), Ok(Value::Float(6.0)));
+    }
+
+    #[test]
+    fn true_and_unknown_is_unknown() {
+        let expr = Expr::BinOp(
+            BinOp::And,
+            Box::new(Expr::Literal(Literal::Bool(true))),
+            Box::new(Expr::Literal(Literal::Ternary(Ternary::Unknown))),
+        );
+        assert_eq!(eval(&expr, &ValueEnv::new(), &mut EvalContext::default()), Ok(Value::Ternary(Ternary::Unknown)));
+    }
+
+    #[test]
+    fn division_by_zero_is_a_runtime_error_not_a_panic() {
+        let expr = Expr::BinOp(BinOp::Div, lit_int(10), lit_int(0));
+        assert!(matches!(eval(&expr, &ValueEnv::new(), &mut EvalContext::default()), Err(CompileError::Runtime(_))));
+    }
+
+    #[test]
+    fn if_evaluates_only_the_taken_branch() {
+        let expr = Expr::If(
+            Box::new(Expr::Literal(Literal::Bool(true))),
+            lit_int(1),
+            lit_int(2),
+        );
+        assert_eq!(eval(&expr, &ValueEnv::new(), &mut EvalContext::default()), Ok(Value::Int(1)));
+    }
+
+    #[test]
+    fn match_on_a_literal_pattern() {
+        let arms = vec![
+            MatchArm {
+                pattern: Pattern::Literal(Literal::Int(1)),
+                guard: None,
+                body: Expr::Literal(Literal::Str("one".into())),
+            },
+            MatchArm {
+                pattern: Pattern::Wildcard,
+                guard: None,
+                body: Expr::Literal(Literal::Str("other".into())),
+            },
+        ];
+        let expr = Expr::Match(lit_int(1), arms);
+        assert_eq!(eval(&expr, &ValueEnv::new(), &mut EvalContext::default()), Ok(Value::String("one".into())));
+    }
+
+    #[test]
+    fn match_wildcard_falls_through_when_earlier_arms_fail() {
+        let arms = vec![
+            MatchArm {
+                pattern: Pattern::Literal(Literal::Int(1)),
+                guard: None,
+                body: Expr::Literal(Literal::Str("one".into())),
+            },
+            MatchArm {
+                pattern: Pattern::Wildcard,
+                guard: None,
+                body: Expr::Literal(Literal::Str("other".into())),
+            },
+        ];
+        let expr = Expr::Match(lit_int(99), arms);
+        assert_eq!(eval(&expr, &ValueEnv::new(), &mut EvalContext::default()), Ok(Value::String("other".into())));
+    }
+
+    #[test]
+    fn match_destructures_a_tuple() {
+        let arms = vec![MatchArm {
+            pattern: Pattern::Tuple(vec![Pattern::Var("a".into()), Pattern::Var("b".into())]),
+            guard: None,
+            body: Expr::BinOp(
+                BinOp::Add,
+                Box::new(Expr::Var("a".into())),
+                Box::new(Expr::Var("b".into())),
+            ),
+        }];
+        let value = Value::Tuple(vec![Value::Int(3), Value::Int(4)]);
+        assert_eq!(eval_match(&value, &arms, &ValueEnv::new(), &mut EvalContext::default()), Ok(Value::Int(7)));
+    }
+
+    #[test]
+    fn non_exhaustive_match_errors_at_runtime() {
+        let arms = vec![MatchArm {
+            pattern: Pattern::Literal(Literal::Int(1)),
+            guard: None,
+            body: Expr::Literal(Literal::Int(1)),
+        }];
+        let expr = Expr::Match(lit_int(2), arms);
+        assert!(matches!(eval(&expr, &ValueEnv::new(), &mut EvalContext::default()), Err(CompileError::Runtime(_))));
+    }
+
+    #[test]
+    fn applying_a_single_argument_lambda() {
+        // (fun x -> x + 1) 5
+        let lambda = Expr::Lambda(LambdaExpr {
+            params: vec!["x".into()],
+            body: Box::new(Expr::BinOp(BinOp::Add, Box::new(Expr::Var("x".into())), lit_int(1))),
+        });
+        let expr = Expr::App(Box::new(lambda), lit_int(5));
+        assert_eq!(eval(&expr, &ValueEnv::new(), &mut EvalContext::default()), Ok(Value::Int(6)));
+    }
+
+    #[test]
+    fn two_argument_application_curries() {
+        // (fun x y -> x + y) 3 4
+        let lambda = Expr::Lambda(LambdaExpr {
+            params: vec!["x".into(), "y".into()],
+            body: Box::new(Expr::BinOp(
+                BinOp::Add,
+                Box::new(Expr::Var("x".into())),
+                Box::new(Expr::Var("y".into())),
+            )),
+        });
+        let applied_once = Expr::App(Box::new(lambda), lit_int(3));
+        let expr = Expr::App(Box::new(applied_once), lit_int(4));
+        assert_eq!(eval(&expr, &ValueEnv::new(), &mut EvalContext::default()), Ok(Value::Int(7)));
+    }
+
+    #[test]
+    fn calling_a_native_function_from_the_prelude() {
+        // mean(xs), with xs = [1, 2, 3] bound ahead of time since the AST
+        // has no list-literal syntax yet.
+        let mut env = prelude();
+        env.insert(
+            "xs".into(),
+            Value::List(im::vector![Value::Int(1), Value::Int(2), Value::Int(3)]),
+        );
+        let expr = Expr::App(Box::new(Expr::Var("mean".into())), Box::new(Expr::Var("xs".into())));
+        assert_eq!(eval(&expr, &env, &mut EvalContext::default()), Ok(Value::Float(2.0)));
+    }
+
+    #[test]
+    fn assert_eq_native_reports_the_compared_values_on_failure() {
+        // assert_eq(1, 2)
+        let env = prelude();
+        let applied_once = Expr::App(Box::new(Expr::Var("assert_eq".into())), lit_int(1));
+        let expr = Expr::App(Box::new(applied_once), lit_int(2));
+        let err = eval(&expr, &env, &mut EvalContext::default()).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains('1') && message.contains('2'), "error message was: {message}");
+    }
+
+    #[test]
+    fn a_two_argument_native_curries_like_a_lambda() {
+        // uniform(0, 1) should build a Dist, applying one argument at a time.
+        let env = prelude();
+        let applied_once = Expr::App(Box::new(Expr::Var("uniform".into())), lit_float(0.0));
+        let expr = Expr::App(Box::new(applied_once), lit_float(1.0));
+        assert!(matches!(eval(&expr, &env, &mut EvalContext::default()), Ok(Value::Dist(_))));
+    }
+
+    #[test]
+    fn sampling_a_uniform_dist_stays_within_its_support() {
+        let expr = Expr::Sample(Box::new(Expr::App(
+            Box::new(Expr::App(Box::new(Expr::Var("uniform".into())), lit_float(0.0))),
+            lit_float(1.0),
+        )));
+        let value = eval(&expr, &prelude(), &mut EvalContext::default()).unwrap();
+        assert!(matches!(value, Value::Float(x) if (0.0..1.0).contains(&x)));
+    }
+
+    #[test]
+    fn sampling_a_non_distribution_is_a_runtime_error() {
+        let expr = Expr::Sample(lit_int(5));
+        assert!(matches!(
+            eval(&expr, &ValueEnv::new(), &mut EvalContext::default()),
+            Err(CompileError::Runtime(_))
+        ));
+    }
+
+    #[test]
+    fn observing_against_a_bernoulli_accumulates_log_weight() {
+        // observe(bernoulli(0.25), true) should add ln(0.25) to the log-weight
+        // and return the observed value.
+        let dist_expr = Expr::App(Box::new(Expr::Var("bernoulli".into())), lit_float(0.25));
+        let expr = Expr::Observe(
+            Box::new(dist_expr),
+            Box::new(Expr::Literal(Literal::Bool(true))),
+        );
+        let mut ctx = EvalContext::default();
+        let value = eval(&expr, &prelude(), &mut ctx).unwrap();
+        assert_eq!(value, Value::Bool(true));
+        assert!((ctx.log_weight - 0.25_f64.ln()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn bet_of_literals_samples_one_of_them() {
+        let expr = Expr::Bet(vec![*lit_int(1), *lit_int(2), *lit_int(3)]);
+        let value = eval(&expr, &ValueEnv::new(), &mut EvalContext::default()).unwrap();
+        assert!(matches!(value, Value::Dist(_)));
+        let Value::Dist(dist) = value else { unreachable!() };
+        assert!(matches!(dist.sample(), Value::Int(1) | Value::Int(2) | Value::Int(3)));
+    }
+
+    #[test]
+    fn do_block_without_a_bind_just_sequences_statements() {
+        // do { let x = 1; x + 1 }
+        let do_expr = DoExpr {
+            stmts: vec![
+                DoStmt::Let("x".into(), *lit_int(1)),
+                DoStmt::Expr(Expr::BinOp(BinOp::Add, Box::new(Expr::Var("x".into())), lit_int(1))),
+            ],
+        };
+        let expr = Expr::Do(do_expr);
+        assert_eq!(eval(&expr, &ValueEnv::new(), &mut EvalContext::default()), Ok(Value::Int(2)));
+    }
+
+    #[test]
+    fn do_block_with_a_bind_is_itself_a_samplable_distribution() {
+        // do { x <- bet{1, 2, 3}; return x + 1 }
+        let do_expr = DoExpr {
+            stmts: vec![
+                DoStmt::Bind("x".into(), Expr::Bet(vec![*lit_int(1), *lit_int(2), *lit_int(3)])),
+                DoStmt::Expr(Expr::Return(Box::new(Expr::BinOp(
+                    BinOp::Add,
+                    Box::new(Expr::Var("x".into())),
+                    lit_int(1),
+                )))),
+            ],
+        };
+        let expr = Expr::Do(do_expr);
+        let value = eval(&expr, &ValueEnv::new(), &mut EvalContext::default()).unwrap();
+        let Value::Dist(dist) = value else { panic!("expected a do-block with a bind to be a Dist") };
+        for _ in 0..20 {
+            let Value::Dist(inner) = dist.sample() else {
+                panic!("`return` should lift the final value into another Dist")
+            };
+            assert!(matches!(inner.sample(), Value::Int(2) | Value::Int(3) | Value::Int(4)));
+        }
+    }
+}
@@ -0,0 +1,14 @@
+//! Inference algorithms driven by betlang's `Expr::Infer`.
+//!
+//! Each `InferMethod` gets its own module here: `vi` for mean-field ADVI,
+//! `mcmc` for Metropolis-Hastings, and `smc` for particle filtering.
+
+pub mod mcmc;
+pub mod posterior;
+pub mod smc;
+pub mod vi;
+
+pub use mcmc::{adaptive_metropolis, metropolis};
+pub use posterior::posterior_predictive;
+pub use smc::particle_filter;
+pub use vi::advi_gaussian;
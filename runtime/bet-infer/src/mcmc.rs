@@ -0,0 +1,149 @@
+use rand::rngs::ThreadRng;
+use rand::Rng;
+use rand_distr::StandardNormal;
+
+/// One Metropolis-Hastings step: proposes `current + N(0, proposal_std)`
+/// componentwise and accepts or rejects it, returning the resulting state,
+/// its log density, and whether the proposal was accepted.
+fn mh_step(
+    log_target: &impl Fn(&[f64]) -> f64,
+    current: &[f64],
+    current_log_density: f64,
+    proposal_std: f64,
+    rng: &mut ThreadRng,
+) -> (Vec<f64>, f64, bool) {
+    let proposal: Vec<f64> = current
+        .iter()
+        .map(|&x| x + proposal_std * rng.sample::<f64, _>(StandardNormal))
+        .collect();
+    let proposal_log_density = log_target(&proposal);
+    let log_accept_ratio = proposal_log_density - current_log_density;
+    if log_accept_ratio >= 0.0 || rng.gen::<f64>().ln() < log_accept_ratio {
+        (proposal, proposal_log_density, true)
+    } else {
+        (current.to_vec(), current_log_density, false)
+    }
+}
+
+/// Random-walk Metropolis-Hastings over a vector-valued latent state.
+///
+/// `log_target` is the unnormalized log density to sample from (typically
+/// `log_likelihood + log_prior` for a model). Proposals are drawn from
+/// `N(current, proposal_std)` componentwise and accepted/rejected by the
+/// usual Metropolis ratio. Returns the full chain, one state per step (the
+/// initial state itself is not included); a rejected step repeats the
+/// current state.
+pub fn metropolis(
+    log_target: impl Fn(&[f64]) -> f64,
+    initial: Vec<f64>,
+    steps: usize,
+    proposal_std: f64,
+) -> Vec<Vec<f64>> {
+    let mut rng = rand::thread_rng();
+    let mut current = initial;
+    let mut current_log_density = log_target(&current);
+    let mut chain = Vec::with_capacity(steps);
+
+    for _ in 0..steps {
+        let (next, next_log_density, _) =
+            mh_step(&log_target, &current, current_log_density, proposal_std, &mut rng);
+        current = next;
+        current_log_density = next_log_density;
+        chain.push(current.clone());
+    }
+    chain
+}
+
+/// Result of [`adaptive_metropolis`]: the post-warmup chain plus the
+/// diagnostics needed to tell whether tuning succeeded.
+pub struct AdaptiveResult {
+    pub chain: Vec<Vec<f64>>,
+    pub acceptance_rate: f64,
+    pub final_proposal_std: f64,
+}
+
+/// The acceptance rate that's optimal for random-walk Metropolis in the
+/// high-dimensional limit (Roberts, Gelman & Gilks 1997).
+const TARGET_ACCEPT_RATE: f64 = 0.234;
+
+/// A Metropolis sampler that tunes `proposal_std` during `warmup` steps to
+/// target a `TARGET_ACCEPT_RATE` acceptance rate, then fixes it for the
+/// `steps`-long sampling phase. Adaptation happens once per block of 50
+/// warmup steps: the step size is multiplied by
+/// `exp(2 * (block_rate - TARGET_ACCEPT_RATE))`, growing when the block's
+/// observed acceptance rate is above the target and shrinking when below.
+pub fn adaptive_metropolis(
+    log_target: impl Fn(&[f64]) -> f64,
+    initial: Vec<f64>,
+    warmup: usize,
+    steps: usize,
+    initial_proposal_std: f64,
+) -> AdaptiveResult {
+    const BLOCK: usize = 50;
+
+    let mut rng = rand::thread_rng();
+    let mut proposal_std = initial_proposal_std;
+    let mut current = initial;
+    let mut current_log_density = log_target(&current);
+
+    let mut accepts_in_block = 0;
+    for i in 0..warmup {
+        let (next, next_log_density, accepted) =
+            mh_step(&log_target, &current, current_log_density, proposal_std, &mut rng);
+        current = next;
+        current_log_density = next_log_density;
+        if accepted {
+            accepts_in_block += 1;
+        }
+        if (i + 1) % BLOCK == 0 {
+            let block_rate = accepts_in_block as f64 / BLOCK as f64;
+            proposal_std *= ((block_rate - TARGET_ACCEPT_RATE) * 2.0).exp();
+            accepts_in_block = 0;
+        }
+    }
+
+    let mut chain = Vec::with_capacity(steps);
+    let mut accepts = 0;
+    for _ in 0..steps {
+        let (next, next_log_density, accepted) =
+            mh_step(&log_target, &current, current_log_density, proposal_std, &mut rng);
+        current = next;
+        current_log_density = next_log_density;
+        if accepted {
+            accepts += 1;
+        }
+        chain.push(current.clone());
+    }
+
+    AdaptiveResult {
+        chain,
+        acceptance_rate: accepts as f64 / steps as f64,
+        final_proposal_std: proposal_std,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metropolis_samples_standard_normal() {
+        let log_target = |x: &[f64]| -0.5 * x[0] * x[0];
+        let chain = metropolis(log_target, vec![0.0], 20_000, 1.0);
+        let mean: f64 = chain.iter().map(|s| s[0]).sum::<f64>() / chain.len() as f64;
+        assert!(mean.abs() < 0.2, "chain mean {mean} not close to 0");
+    }
+
+    #[test]
+    fn adaptive_metropolis_tunes_to_target_acceptance_rate() {
+        let log_target = |x: &[f64]| -0.5 * x[0] * x[0];
+        // Start with a deliberately bad step size so adaptation has to work.
+        let result = adaptive_metropolis(log_target, vec![0.0], 5_000, 10_000, 50.0);
+        assert!(
+            (result.acceptance_rate - TARGET_ACCEPT_RATE).abs() < 0.1,
+            "acceptance rate {} not close to target {}",
+            result.acceptance_rate,
+            TARGET_ACCEPT_RATE
+        );
+    }
+}
@@ -0,0 +1,43 @@
+use rand::Rng;
+
+use bet_rt::Value;
+
+/// The standard last step of a Bayesian workflow: draw a parameter value
+/// from the posterior, feed it to the model to get one predictive draw,
+/// and repeat `n` times. This approximates the posterior predictive
+/// distribution by Monte Carlo.
+pub fn posterior_predictive(posterior_samples: &[Value], model: impl Fn(&Value) -> Value, n: usize) -> Vec<Value> {
+    let mut rng = rand::thread_rng();
+    if posterior_samples.is_empty() {
+        return Vec::new();
+    }
+    (0..n)
+        .map(|_| {
+            let idx = rng.gen_range(0..posterior_samples.len());
+            model(&posterior_samples[idx])
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bet_rt::random::normal;
+
+    #[test]
+    fn predictions_from_fitted_normal_fall_in_plausible_range() {
+        let posterior_means: Vec<Value> = vec![Value::Float(4.8), Value::Float(5.0), Value::Float(5.2)];
+        let model = |mean: &Value| -> Value {
+            let Value::Float(mean) = mean else { panic!("expected a Float posterior sample") };
+            let Value::Dist(dist) = normal(*mean, 1.0).unwrap() else { unreachable!() };
+            dist.sample()
+        };
+
+        let predictions = posterior_predictive(&posterior_means, model, 500);
+        assert_eq!(predictions.len(), 500);
+        for p in &predictions {
+            let Value::Float(x) = p else { panic!("expected a Float prediction") };
+            assert!((0.0..10.0).contains(x), "prediction {x} outside plausible range");
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use rand::rngs::ThreadRng;
+use rand::Rng;
+
+/// A single weighted particle in the filter's state.
+#[derive(Debug, Clone)]
+pub struct Particle {
+    pub state: f64,
+    pub weight: f64,
+}
+
+/// `1 / sum(normalized_weight^2)`: how many particles are "effectively"
+/// contributing to the estimate. Drops toward 1 as weights collapse onto
+/// a single particle (degeneracy).
+fn effective_sample_size(weights: &[f64]) -> f64 {
+    let sum: f64 = weights.iter().sum();
+    let sum_sq: f64 = weights.iter().map(|w| (w / sum).powi(2)).sum();
+    1.0 / sum_sq
+}
+
+/// Systematic resampling: draws `n` indices from `weights` using a single
+/// random offset and evenly spaced strata, which has lower variance than
+/// resampling each index independently.
+fn systematic_resample(weights: &[f64], rng: &mut ThreadRng) -> Vec<usize> {
+    let n = weights.len();
+    let sum: f64 = weights.iter().sum();
+    let mut cumulative = Vec::with_capacity(n);
+    let mut running = 0.0;
+    for w in weights {
+        running += w / sum;
+        cumulative.push(running);
+    }
+
+    let offset: f64 = rng.gen::<f64>() / n as f64;
+    let mut indices = Vec::with_capacity(n);
+    let mut j = 0;
+    for i in 0..n {
+        let target = offset + i as f64 / n as f64;
+        while cumulative[j] < target && j + 1 < n {
+            j += 1;
+        }
+        indices.push(j);
+    }
+    indices
+}
+
+/// A sequential Monte Carlo / particle filter.
+///
+/// `init` draws an initial particle state; `transition` advances a state
+/// one step; `log_weight(state, observation)` is the log-likelihood of an
+/// observation given a state. Resamples via [`systematic_resample`]
+/// whenever the effective sample size drops below
+/// `ess_threshold * n_particles`. Returns the filtered mean state at each
+/// observation.
+pub fn particle_filter(
+    n_particles: usize,
+    init: impl Fn(&mut ThreadRng) -> f64,
+    transition: impl Fn(f64, &mut ThreadRng) -> f64,
+    log_weight: impl Fn(f64, f64) -> f64,
+    observations: &[f64],
+    ess_threshold: f64,
+) -> Vec<f64> {
+    let mut rng = rand::thread_rng();
+    let mut particles: Vec<Particle> = (0..n_particles)
+        .map(|_| Particle {
+            state: init(&mut rng),
+            weight: 1.0 / n_particles as f64,
+        })
+        .collect();
+
+    let mut filtered_means = Vec::with_capacity(observations.len());
+
+    for &obs in observations {
+        for p in &mut particles {
+            p.state = transition(p.state, &mut rng);
+        }
+        let log_weights: Vec<f64> = particles.iter().map(|p| log_weight(p.state, obs)).collect();
+        let max_log_weight = log_weights.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        for (p, lw) in particles.iter_mut().zip(&log_weights) {
+            p.weight *= (lw - max_log_weight).exp();
+        }
+        let weight_sum: f64 = particles.iter().map(|p| p.weight).sum();
+        for p in &mut particles {
+            p.weight /= weight_sum;
+        }
+
+        let weights: Vec<f64> = particles.iter().map(|p| p.weight).collect();
+        let mean = particles.iter().map(|p| p.state * p.weight).sum();
+        filtered_means.push(mean);
+
+        if effective_sample_size(&weights) < ess_threshold * n_particles as f64 {
+            let indices = systematic_resample(&weights, &mut rng);
+            particles = indices
+                .into_iter()
+                .map(|i| Particle {
+                    state: particles[i].state,
+                    weight: 1.0 / n_particles as f64,
+                })
+                .collect();
+        }
+    }
+
+    filtered_means
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn particle_filter_tracks_linear_gaussian_state() {
+        // x_t = x_{t-1} + 1 + U(-0.1, 0.1), y_t = x_t + N(0, 0.5)
+        let true_states = [0.0_f64, 1.0, 2.0, 3.0, 4.0];
+        let observations: Vec<f64> = true_states.to_vec();
+
+        let log_gaussian = |x: f64, mean: f64, std: f64| -> f64 {
+            -0.5 * ((x - mean) / std).powi(2) - std.ln()
+        };
+
+        let means = particle_filter(
+            2_000,
+            |rng| rng.gen_range(-1.0..1.0),
+            |x, rng| x + 1.0 + rng.gen_range(-0.1..0.1),
+            |state, obs| log_gaussian(obs, state, 0.5),
+            &observations,
+            0.5,
+        );
+
+        let last = *means.last().unwrap();
+        assert!((last - true_states[4]).abs() < 1.0, "filtered mean {last} far from {}", true_states[4]);
+    }
+}
@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::value::{NativeFunction, Value};
+
+fn as_usize(v: &Value, pos: &str) -> Result<usize, String> {
+    match v {
+        Value::Int(i) if *i >= 0 => Ok(*i as usize),
+        other => Err(format!("expected a non-negative integer for {pos}, found {other}")),
+    }
+}
+
+fn as_float(v: &Value, pos: &str) -> Result<f64, String> {
+    match v {
+        Value::Float(x) => Ok(*x),
+        Value::Int(i) => Ok(*i as f64),
+        other => Err(format!("expected a number for {pos}, found {other}")),
+    }
+}
+
+fn native(name: &str, arity: usize, func: impl Fn(&[Value]) -> Result<Value, String> + Send + Sync + 'static) -> (String, NativeFunction) {
+    (
+        name.to_string(),
+        NativeFunction {
+            name: name.to_string(),
+            arity,
+            func: Arc::new(func),
+        },
+    )
+}
+
+/// In-program testing natives for self-testing betlang scripts: `assert`,
+/// `assert_eq`, `assert_approx`, and `assert_mean_near`, all raising a
+/// runtime error with a descriptive message on failure and returning
+/// `Unit` on success.
+pub fn native_functions() -> HashMap<String, NativeFunction> {
+    let mut m = HashMap::new();
+    let (name, f) = native("assert", 1, |args| match &args[0] {
+        Value::Bool(true) => Ok(Value::Unit),
+        Value::Bool(false) => Err("assertion failed".to_string()),
+        other => Err(format!("assert expects a Bool, found `{other}`")),
+    });
+    m.insert(name, f);
+    let (name, f) = native("assert_eq", 2, |args| {
+        if args[0] == args[1] {
+            Ok(Value::Unit)
+        } else {
+            Err(format!("assertion failed: `{}` != `{}`", args[0], args[1]))
+        }
+    });
+    m.insert(name, f);
+    let (name, f) = native("assert_approx", 3, |args| {
+        let a = as_float(&args[0], "the first value")?;
+        let b = as_float(&args[1], "the second value")?;
+        let eps = as_float(&args[2], "eps")?;
+        if (a - b).abs() <= eps {
+            Ok(Value::Unit)
+        } else {
+            Err(format!("assertion failed: `{a}` is not within `{eps}` of `{b}`"))
+        }
+    });
+    m.insert(name, f);
+    let (name, f) = native("assert_mean_near", 4, |args| {
+        let dist = match &args[0] {
+            Value::Dist(d) => d,
+            other => return Err(format!("assert_mean_near expects a distribution, found `{other}`")),
+        };
+        let expected = as_float(&args[1], "expected")?;
+        let n = as_usize(&args[2], "n")?;
+        let tol = as_float(&args[3], "tol")?;
+        if n == 0 {
+            return Err("assert_mean_near needs at least one sample".to_string());
+        }
+        let sum: f64 = (0..n)
+            .map(|_| as_float(&dist.sample(), "a sample"))
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter()
+            .sum();
+        let observed = sum / n as f64;
+        if (observed - expected).abs() <= tol {
+            Ok(Value::Unit)
+        } else {
+            Err(format!(
+                "assertion failed: mean of {n} samples was `{observed}`, not within `{tol}` of `{expected}`"
+            ))
+        }
+    });
+    m.insert(name, f);
+    m
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(name: &str, args: &[Value]) -> Result<Value, String> {
+        (native_functions()[name].func)(args)
+    }
+
+    #[test]
+    fn assert_passes_on_true_and_fails_on_false() {
+        assert_eq!(call("assert", &[Value::Bool(true)]), Ok(Value::Unit));
+        assert_eq!(call("assert", &[Value::Bool(false)]), Err("assertion failed".to_string()));
+    }
+
+    #[test]
+    fn assert_eq_reports_both_compared_values_on_failure() {
+        let err = call("assert_eq", &[Value::Int(1), Value::Int(2)]).unwrap_err();
+        assert!(err.contains('1') && err.contains('2'), "error message was: {err}");
+    }
+
+    #[test]
+    fn assert_eq_passes_for_equal_values() {
+        assert_eq!(call("assert_eq", &[Value::Int(5), Value::Int(5)]), Ok(Value::Unit));
+    }
+
+    #[test]
+    fn assert_approx_allows_values_within_eps() {
+        assert_eq!(
+            call("assert_approx", &[Value::Float(1.0), Value::Float(1.0001), Value::Float(0.01)]),
+            Ok(Value::Unit)
+        );
+    }
+
+    #[test]
+    fn assert_approx_fails_outside_eps_and_reports_values() {
+        let err = call("assert_approx", &[Value::Float(1.0), Value::Float(2.0), Value::Float(0.01)]).unwrap_err();
+        assert!(err.contains('1') && err.contains('2'), "error message was: {err}");
+    }
+
+    #[test]
+    fn assert_mean_near_passes_for_a_uniform_distribution_mean() {
+        let dist = crate::random::uniform(0.0, 1.0).unwrap();
+        let result = call(
+            "assert_mean_near",
+            &[dist, Value::Float(0.5), Value::Int(10_000), Value::Float(0.05)],
+        );
+        assert_eq!(result, Ok(Value::Unit));
+    }
+
+    #[test]
+    fn assert_mean_near_fails_and_reports_the_observed_mean() {
+        let dist = crate::random::uniform(0.0, 1.0).unwrap();
+        let err = call(
+            "assert_mean_near",
+            &[dist, Value::Float(100.0), Value::Int(1000), Value::Float(0.01)],
+        )
+        .unwrap_err();
+        assert!(err.contains("100"), "error message was: {err}");
+    }
+}
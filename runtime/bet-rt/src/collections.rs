@@ -0,0 +1,210 @@
+//! Conversion natives between betlang's three container values: `List`,
+//! `Set`, and `Map`.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use im::HashMap as ImMap;
+
+use crate::value::{NativeFunction, Value};
+
+/// A `Set`'s elements, in their (unspecified, hash-based) iteration order.
+pub fn set_to_list(set: &ImMap<Value, ()>) -> Vec<Value> {
+    set.keys().cloned().collect()
+}
+
+/// Deduplicates `list` into a `Set`.
+pub fn list_to_set(list: &[Value]) -> ImMap<Value, ()> {
+    list.iter().cloned().map(|v| (v, ())).collect()
+}
+
+/// A `Map`'s entries as `[key, value]` pairs, sorted by key for
+/// deterministic output (see [`crate::value::sorted_map_entries`]).
+pub fn map_to_list(map: &ImMap<String, Value>) -> Vec<Value> {
+    crate::value::sorted_map_entries(map)
+        .into_iter()
+        .map(|(k, v)| Value::Tuple(vec![Value::String(k.clone()), v.clone()]))
+        .collect()
+}
+
+/// Builds a `Map` from a list of `[key, value]` pairs (as `Tuple`s or
+/// two-element `List`s); a later pair overwrites an earlier one with the
+/// same key.
+pub fn list_to_map(list: &[Value]) -> Result<ImMap<String, Value>, String> {
+    let mut map = ImMap::new();
+    for entry in list {
+        let pair = match entry {
+            Value::Tuple(items) if items.len() == 2 => items.clone(),
+            Value::List(items) if items.len() == 2 => items.iter().cloned().collect(),
+            other => return Err(format!("expected a [key, value] pair, found {other}")),
+        };
+        let key = match &pair[0] {
+            Value::String(s) => s.clone(),
+            other => return Err(format!("expected a String key, found {other}")),
+        };
+        map.insert(key, pair[1].clone());
+    }
+    Ok(map)
+}
+
+/// Every element in either `a` or `b`.
+pub fn set_union(a: &ImMap<Value, ()>, b: &ImMap<Value, ()>) -> ImMap<Value, ()> {
+    a.clone().union(b.clone())
+}
+
+/// Every element in both `a` and `b`.
+pub fn set_intersection(a: &ImMap<Value, ()>, b: &ImMap<Value, ()>) -> ImMap<Value, ()> {
+    a.iter().filter(|(v, _)| b.contains_key(v)).map(|(v, _)| (v.clone(), ())).collect()
+}
+
+/// Every element in `a` that is not also in `b`.
+pub fn set_difference(a: &ImMap<Value, ()>, b: &ImMap<Value, ()>) -> ImMap<Value, ()> {
+    a.iter().filter(|(v, _)| !b.contains_key(v)).map(|(v, _)| (v.clone(), ())).collect()
+}
+
+/// Is every element of `a` also in `b`?
+pub fn is_subset(a: &ImMap<Value, ()>, b: &ImMap<Value, ()>) -> bool {
+    a.keys().all(|v| b.contains_key(v))
+}
+
+fn set_of(v: &Value) -> Result<ImMap<Value, ()>, String> {
+    match v {
+        Value::Set(s) => Ok(s.clone()),
+        other => Err(format!("expected a Set, found {other}")),
+    }
+}
+
+fn native(name: &str, arity: usize, func: impl Fn(&[Value]) -> Result<Value, String> + Send + Sync + 'static) -> (String, NativeFunction) {
+    (
+        name.to_string(),
+        NativeFunction {
+            name: name.to_string(),
+            arity,
+            func: Arc::new(func),
+        },
+    )
+}
+
+pub fn native_functions() -> HashMap<String, NativeFunction> {
+    let mut m = HashMap::new();
+    let (name, f) = native("set_to_list", 1, |args| match &args[0] {
+        Value::Set(s) => Ok(Value::List(set_to_list(s).into_iter().collect())),
+        other => Err(format!("expected a Set, found {other}")),
+    });
+    m.insert(name, f);
+    let (name, f) = native("list_to_set", 1, |args| match &args[0] {
+        Value::List(items) => Ok(Value::Set(list_to_set(&items.iter().cloned().collect::<Vec<_>>()))),
+        other => Err(format!("expected a List, found {other}")),
+    });
+    m.insert(name, f);
+    let (name, f) = native("map_to_list", 1, |args| match &args[0] {
+        Value::Map(m) => Ok(Value::List(map_to_list(m).into_iter().collect())),
+        other => Err(format!("expected a Map, found {other}")),
+    });
+    m.insert(name, f);
+    let (name, f) = native("list_to_map", 1, |args| match &args[0] {
+        Value::List(items) => Ok(Value::Map(list_to_map(&items.iter().cloned().collect::<Vec<_>>())?)),
+        other => Err(format!("expected a List, found {other}")),
+    });
+    m.insert(name, f);
+    let (name, f) = native("set_union", 2, |args| Ok(Value::Set(set_union(&set_of(&args[0])?, &set_of(&args[1])?))));
+    m.insert(name, f);
+    let (name, f) = native("set_intersection", 2, |args| Ok(Value::Set(set_intersection(&set_of(&args[0])?, &set_of(&args[1])?))));
+    m.insert(name, f);
+    let (name, f) = native("set_difference", 2, |args| Ok(Value::Set(set_difference(&set_of(&args[0])?, &set_of(&args[1])?))));
+    m.insert(name, f);
+    let (name, f) = native("is_subset", 2, |args| Ok(Value::Bool(is_subset(&set_of(&args[0])?, &set_of(&args[1])?))));
+    m.insert(name, f);
+    m
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn list_to_set_deduplicates_elements() {
+        let list = vec![Value::Int(1), Value::Int(2), Value::Int(1)];
+        let set = list_to_set(&list);
+        assert_eq!(set.len(), 2);
+    }
+
+    #[test]
+    fn set_to_list_round_trips_through_list_to_set() {
+        let set = list_to_set(&[Value::Int(1), Value::Int(2), Value::Int(3)]);
+        let mut list = set_to_list(&set);
+        list.sort_by_key(|v| match v {
+            Value::Int(i) => *i,
+            _ => 0,
+        });
+        assert_eq!(list, vec![Value::Int(1), Value::Int(2), Value::Int(3)]);
+    }
+
+    #[test]
+    fn map_to_list_and_back_preserves_entries() {
+        let mut map = ImMap::new();
+        map.insert("a".to_string(), Value::Int(1));
+        map.insert("b".to_string(), Value::Int(2));
+
+        let list = map_to_list(&map);
+        assert_eq!(
+            list,
+            vec![
+                Value::Tuple(vec![Value::String("a".to_string()), Value::Int(1)]),
+                Value::Tuple(vec![Value::String("b".to_string()), Value::Int(2)]),
+            ]
+        );
+
+        let round_tripped = list_to_map(&list).unwrap();
+        assert_eq!(round_tripped, map);
+    }
+
+    #[test]
+    fn list_to_map_rejects_a_non_string_key() {
+        let list = vec![Value::Tuple(vec![Value::Int(1), Value::Int(2)])];
+        assert!(list_to_map(&list).is_err());
+    }
+
+    #[test]
+    fn set_union_combines_both_sets_without_duplicates() {
+        let a = list_to_set(&[Value::Int(1), Value::Int(2)]);
+        let b = list_to_set(&[Value::Int(2), Value::Int(3)]);
+        assert_eq!(set_union(&a, &b).len(), 3);
+    }
+
+    #[test]
+    fn set_intersection_keeps_only_shared_elements() {
+        let a = list_to_set(&[Value::Int(1), Value::Int(2)]);
+        let b = list_to_set(&[Value::Int(2), Value::Int(3)]);
+        let intersection = set_intersection(&a, &b);
+        assert_eq!(intersection.len(), 1);
+        assert!(intersection.contains_key(&Value::Int(2)));
+    }
+
+    #[test]
+    fn set_difference_keeps_only_elements_unique_to_a() {
+        let a = list_to_set(&[Value::Int(1), Value::Int(2)]);
+        let b = list_to_set(&[Value::Int(2), Value::Int(3)]);
+        let difference = set_difference(&a, &b);
+        assert_eq!(difference.len(), 1);
+        assert!(difference.contains_key(&Value::Int(1)));
+    }
+
+    #[test]
+    fn is_subset_checks_containment_in_either_direction() {
+        let small = list_to_set(&[Value::Int(1)]);
+        let large = list_to_set(&[Value::Int(1), Value::Int(2)]);
+        assert!(is_subset(&small, &large));
+        assert!(!is_subset(&large, &small));
+    }
+
+    #[test]
+    fn list_to_map_lets_a_later_pair_overwrite_an_earlier_one() {
+        let list = vec![
+            Value::Tuple(vec![Value::String("a".to_string()), Value::Int(1)]),
+            Value::Tuple(vec![Value::String("a".to_string()), Value::Int(2)]),
+        ];
+        let map = list_to_map(&list).unwrap();
+        assert_eq!(map.get("a"), Some(&Value::Int(2)));
+    }
+}
@@ -0,0 +1,146 @@
+//! CSV serialization for record lists (`Value::List` of `Value::Map`).
+
+use crate::value::Value;
+
+/// Options controlling [`stringify`]'s output.
+#[derive(Default)]
+pub struct CsvOptions {
+    /// Explicit column order. When `None`, columns are the union of all
+    /// record keys in sorted order, so output is deterministic regardless
+    /// of the underlying map's iteration order.
+    pub columns: Option<Vec<String>>,
+    /// The token written for `Value::Unit` and for fields missing from a
+    /// given record, so callers can tell "absent" from "empty string".
+    pub null_token: String,
+}
+
+fn column_order(records: &[Value], explicit: &Option<Vec<String>>) -> Vec<String> {
+    if let Some(columns) = explicit {
+        return columns.clone();
+    }
+    let mut columns = std::collections::BTreeSet::new();
+    for record in records {
+        if let Value::Map(fields) = record {
+            for key in fields.keys() {
+                columns.insert(key.clone());
+            }
+        }
+    }
+    columns.into_iter().collect()
+}
+
+fn escape_field(field: &str) -> String {
+    if field.contains(',') || field.contains('"') || field.contains('\n') {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Splits one CSV line into fields, undoing the quoting [`escape_field`]
+/// applies: a field wrapped in double quotes may contain commas, and `""`
+/// inside a quoted field is an escaped literal quote. Embedded newlines
+/// within a quoted field aren't supported, since callers (e.g.
+/// [`crate::io::CsvChunks`]) read one record per line.
+pub(crate) fn parse_row(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+fn render_cell(value: Option<&Value>, null_token: &str) -> String {
+    match value {
+        None => null_token.to_string(),
+        Some(Value::Unit) => null_token.to_string(),
+        Some(other) => other.to_string(),
+    }
+}
+
+/// Renders `records` as CSV text, with a header row followed by one row per
+/// record. Column order is deterministic (see [`CsvOptions::columns`]), and
+/// missing fields render as `null_token` just like `Value::Unit` does.
+pub fn stringify(records: &[Value], options: &CsvOptions) -> String {
+    let columns = column_order(records, &options.columns);
+    let mut out = columns.iter().map(|c| escape_field(c)).collect::<Vec<_>>().join(",");
+    out.push('\n');
+
+    for record in records {
+        let fields = match record {
+            Value::Map(fields) => Some(fields),
+            _ => None,
+        };
+        let row: Vec<String> = columns
+            .iter()
+            .map(|col| {
+                let cell = fields.and_then(|f| f.get(col));
+                escape_field(&render_cell(cell, &options.null_token))
+            })
+            .collect();
+        out.push_str(&row.join(","));
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use im::HashMap as ImMap;
+
+    fn record(pairs: &[(&str, Value)]) -> Value {
+        let mut m = ImMap::new();
+        for (k, v) in pairs {
+            m.insert(k.to_string(), v.clone());
+        }
+        Value::Map(m)
+    }
+
+    #[test]
+    fn repeated_runs_produce_identically_ordered_csv() {
+        let records = vec![
+            record(&[("b", Value::Int(2)), ("a", Value::Int(1))]),
+            record(&[("a", Value::Int(3)), ("b", Value::Int(4))]),
+        ];
+        let options = CsvOptions::default();
+        let first = stringify(&records, &options);
+        let second = stringify(&records, &options);
+        assert_eq!(first, second);
+        assert_eq!(first, "a,b\n1,2\n3,4\n");
+    }
+
+    #[test]
+    fn parse_row_round_trips_a_quoted_field_with_a_comma_and_an_escaped_quote() {
+        let row = escape_field(r#"say "hi", bob"#);
+        assert_eq!(parse_row(&row), vec![r#"say "hi", bob"#.to_string()]);
+    }
+
+    #[test]
+    fn parse_row_splits_plain_unquoted_fields_on_commas() {
+        assert_eq!(parse_row("a,b,c"), vec!["a".to_string(), "b".to_string(), "c".to_string()]);
+    }
+
+    #[test]
+    fn unit_renders_as_the_configured_null_token() {
+        let records = vec![record(&[("a", Value::Unit), ("b", Value::Int(1))])];
+        let options = CsvOptions {
+            columns: Some(vec!["a".to_string(), "b".to_string()]),
+            null_token: "NULL".to_string(),
+        };
+        let csv = stringify(&records, &options);
+        assert_eq!(csv, "a,b\nNULL,1\n");
+    }
+}
@@ -0,0 +1,515 @@
+//! DataFrame-like operations over lists of record [`Value::Map`]s.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use im::{HashMap as ImMap, Vector as ImVector};
+
+use crate::error::{SerialError, SerialResult};
+use crate::stats::summary;
+use crate::value::{NativeFunction, Value};
+
+/// The number of most-frequent values kept for a categorical column.
+const TOP_K: usize = 5;
+
+fn collect_columns(records: &[Value]) -> Vec<(String, Vec<Value>)> {
+    let mut order = Vec::new();
+    let mut columns: ImMap<String, Vec<Value>> = ImMap::new();
+    for record in records {
+        let Value::Map(fields) = record else { continue };
+        for (key, value) in fields {
+            if !columns.contains_key(key) {
+                order.push(key.clone());
+            }
+            columns.entry(key.clone()).or_default().push(value.clone());
+        }
+    }
+    order.into_iter().map(|k| (k.clone(), columns.remove(&k).unwrap_or_default())).collect()
+}
+
+fn is_numeric(v: &Value) -> bool {
+    matches!(v, Value::Float(_) | Value::Int(_))
+}
+
+/// Counts occurrences of each distinct value, most-frequent first (ties
+/// broken by rendered form, for deterministic output regardless of
+/// `im::HashMap`'s iteration order).
+pub fn value_counts(values: &[Value]) -> Vec<(Value, i64)> {
+    let mut counts: ImMap<Value, i64> = ImMap::new();
+    for v in values {
+        *counts.entry(v.clone()).or_insert(0) += 1;
+    }
+    let mut counts: Vec<(Value, i64)> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.to_string().cmp(&b.0.to_string())));
+    counts
+}
+
+/// Counts occurrences of each distinct value (via [`value_counts`]) and
+/// returns the `TOP_K` most frequent, most-frequent first, each rendered
+/// by [`label`].
+fn top_value_counts(values: &[Value]) -> Vec<(String, i64)> {
+    value_counts(values).into_iter().take(TOP_K).map(|(v, count)| (label(&v), count)).collect()
+}
+
+fn describe_column(name: &str, values: &[Value]) -> Value {
+    let mut table = ImMap::new();
+    table.insert("column".to_string(), Value::String(name.to_string()));
+
+    if !values.is_empty() && values.iter().all(is_numeric) {
+        if let Some(stats) = summary(values) {
+            for (k, v) in stats {
+                table.insert(k, v);
+            }
+        }
+    } else {
+        let counts = top_value_counts(values);
+        let counts_map: ImVector<Value> = counts
+            .into_iter()
+            .map(|(value, count)| {
+                Value::Tuple(vec![Value::String(value), Value::Int(count)])
+            })
+            .collect();
+        table.insert("value_counts".to_string(), Value::List(counts_map));
+    }
+    Value::Map(table)
+}
+
+/// Mirrors pandas' `describe`: one summary per column, numeric summaries
+/// (mean, std, quartiles, ...) for numeric columns and top-k value counts
+/// for everything else.
+pub fn describe(records: &[Value]) -> Vec<Value> {
+    collect_columns(records)
+        .into_iter()
+        .map(|(name, values)| describe_column(&name, &values))
+        .collect()
+}
+
+fn field_names(fields: &Value) -> Result<Vec<String>, String> {
+    match fields {
+        Value::List(items) => items
+            .iter()
+            .map(|v| match v {
+                Value::String(s) => Ok(s.clone()),
+                other => Err(format!("expected a field name string, found {other}")),
+            })
+            .collect(),
+        other => Err(format!("expected a list of field names, found {other}")),
+    }
+}
+
+fn map_of(v: &Value) -> Result<ImMap<String, Value>, String> {
+    match v {
+        Value::Map(m) => Ok(m.clone()),
+        other => Err(format!("expected a map, found {other}")),
+    }
+}
+
+fn int_of(v: &Value) -> Result<i64, String> {
+    match v {
+        Value::Int(i) => Ok(*i),
+        other => Err(format!("expected an Int, found {other}")),
+    }
+}
+
+fn records_of(v: &Value) -> Result<Vec<Value>, String> {
+    match v {
+        Value::List(items) => Ok(items.iter().cloned().collect()),
+        other => Err(format!("expected a list of records, found {other}")),
+    }
+}
+
+/// Keeps only the named fields of each record, dropping the rest. A named
+/// field missing from a record is simply absent from the output, not an
+/// error.
+pub fn select(records: &[Value], fields: &[String]) -> Vec<Value> {
+    records
+        .iter()
+        .map(|record| {
+            let Value::Map(row) = record else { return record.clone() };
+            let mut kept = ImMap::new();
+            for field in fields {
+                if let Some(value) = row.get(field) {
+                    kept.insert(field.clone(), value.clone());
+                }
+            }
+            Value::Map(kept)
+        })
+        .collect()
+}
+
+/// The inverse of [`select`]: keeps every field except the named ones.
+pub fn drop_columns(records: &[Value], fields: &[String]) -> Vec<Value> {
+    records
+        .iter()
+        .map(|record| {
+            let Value::Map(row) = record else { return record.clone() };
+            let mut kept = row.clone();
+            for field in fields {
+                kept.remove(field);
+            }
+            Value::Map(kept)
+        })
+        .collect()
+}
+
+/// Keeps the records for which `predicate` returns `true`. The
+/// `filter_records` native applies the same filter itself and accepts only
+/// a [`Value::Native`] predicate; a betlang closure is rejected there.
+pub fn filter_records(records: &[Value], predicate: impl Fn(&Value) -> bool) -> Vec<Value> {
+    records.iter().filter(|r| predicate(r)).cloned().collect()
+}
+
+/// Generates `n` synthetic records, each field sampled independently from
+/// the matching distribution in `schema`.
+pub fn gen_dataset(schema: &ImMap<String, Value>, n: usize) -> Result<Vec<Value>, String> {
+    let fields: Vec<(&String, &Arc<crate::random::Distribution>)> = schema
+        .iter()
+        .map(|(name, dist)| match dist {
+            Value::Dist(d) => Ok((name, d)),
+            other => Err(format!("expected a distribution for field {name}, found {other}")),
+        })
+        .collect::<Result<_, String>>()?;
+
+    Ok((0..n)
+        .map(|_| {
+            let mut row = ImMap::new();
+            for (name, dist) in &fields {
+                row.insert((*name).clone(), dist.sample());
+            }
+            Value::Map(row)
+        })
+        .collect())
+}
+
+/// Merges `b`'s fields onto `a`, shallowly: a key present in both keeps
+/// `b`'s value, whatever type it is. Neither input is mutated.
+pub fn merge(a: &ImMap<String, Value>, b: &ImMap<String, Value>) -> ImMap<String, Value> {
+    let mut merged = a.clone();
+    for (key, value) in b {
+        merged.insert(key.clone(), value.clone());
+    }
+    merged
+}
+
+/// Like [`merge`], but when a key is a `Value::Map` in both `a` and `b`,
+/// merges those nested maps recursively instead of letting `b`'s value
+/// overwrite `a`'s outright.
+pub fn deep_merge(a: &ImMap<String, Value>, b: &ImMap<String, Value>) -> ImMap<String, Value> {
+    let mut merged = a.clone();
+    for (key, b_value) in b {
+        let value = match (merged.get(key), b_value) {
+            (Some(Value::Map(a_inner)), Value::Map(b_inner)) => Value::Map(deep_merge(a_inner, b_inner)),
+            _ => b_value.clone(),
+        };
+        merged.insert(key.clone(), value);
+    }
+    merged
+}
+
+/// A contingency table: co-occurrence counts of `row_labels[i]` with
+/// `col_labels[j]` in `counts[i][j]`, suitable for a confusion-matrix plot
+/// or a chi-square test.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Table {
+    pub row_labels: Vec<String>,
+    pub col_labels: Vec<String>,
+    pub counts: Vec<Vec<u64>>,
+}
+
+/// Renders a value as a categorical label: a plain string keeps its raw
+/// content (unlike `Display`, which quotes it), everything else falls back
+/// to its usual rendering.
+fn label(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Cross-tabulates two categorical fields of `records` into a [`Table`] of
+/// co-occurrence counts.
+pub fn crosstab(records: &[Value], row_field: &str, col_field: &str) -> SerialResult<Table> {
+    let mut row_labels: Vec<String> = Vec::new();
+    let mut col_labels: Vec<String> = Vec::new();
+    let mut pairs: Vec<(String, String)> = Vec::new();
+
+    for record in records {
+        let Value::Map(fields) = record else {
+            return Err(SerialError::Decode(format!("expected a record map, found {record}")));
+        };
+        let row = label(
+            fields
+                .get(row_field)
+                .ok_or_else(|| SerialError::Decode(format!("record missing field {row_field}")))?,
+        );
+        let col = label(
+            fields
+                .get(col_field)
+                .ok_or_else(|| SerialError::Decode(format!("record missing field {col_field}")))?,
+        );
+        if !row_labels.contains(&row) {
+            row_labels.push(row.clone());
+        }
+        if !col_labels.contains(&col) {
+            col_labels.push(col.clone());
+        }
+        pairs.push((row, col));
+    }
+
+    let mut counts = vec![vec![0u64; col_labels.len()]; row_labels.len()];
+    for (row, col) in pairs {
+        let i = row_labels.iter().position(|r| r == &row).unwrap();
+        let j = col_labels.iter().position(|c| c == &col).unwrap();
+        counts[i][j] += 1;
+    }
+
+    Ok(Table { row_labels, col_labels, counts })
+}
+
+fn native(name: &str, arity: usize, func: impl Fn(&[Value]) -> Result<Value, String> + Send + Sync + 'static) -> (String, NativeFunction) {
+    (
+        name.to_string(),
+        NativeFunction {
+            name: name.to_string(),
+            arity,
+            func: Arc::new(func),
+        },
+    )
+}
+
+pub fn native_functions() -> HashMap<String, NativeFunction> {
+    let mut m = HashMap::new();
+    let (name, f) = native("select", 2, |args| {
+        let records = records_of(&args[0])?;
+        let fields = field_names(&args[1])?;
+        Ok(Value::List(select(&records, &fields).into_iter().collect()))
+    });
+    m.insert(name, f);
+    let (name, f) = native("drop_columns", 2, |args| {
+        let records = records_of(&args[0])?;
+        let fields = field_names(&args[1])?;
+        Ok(Value::List(drop_columns(&records, &fields).into_iter().collect()))
+    });
+    m.insert(name, f);
+    let (name, f) = native("filter_records", 2, |args| {
+        let records = records_of(&args[0])?;
+        let predicate = match &args[1] {
+            Value::Native(nf) => nf.clone(),
+            other => return Err(format!("expected a predicate function, found {other}")),
+        };
+        let mut kept = Vec::new();
+        for record in records {
+            match (predicate.func)(std::slice::from_ref(&record))? {
+                Value::Bool(b) => {
+                    if b {
+                        kept.push(record);
+                    }
+                }
+                other => return Err(format!("predicate must return a bool, found {other}")),
+            }
+        }
+        Ok(Value::List(kept.into_iter().collect()))
+    });
+    m.insert(name, f);
+    let (name, f) = native("gen_dataset", 2, |args| {
+        let schema = map_of(&args[0])?;
+        let n = int_of(&args[1])?;
+        Ok(Value::List(gen_dataset(&schema, n.max(0) as usize)?.into_iter().collect()))
+    });
+    m.insert(name, f);
+    let (name, f) = native("merge", 2, |args| Ok(Value::Map(merge(&map_of(&args[0])?, &map_of(&args[1])?))));
+    m.insert(name, f);
+    let (name, f) = native("deep_merge", 2, |args| Ok(Value::Map(deep_merge(&map_of(&args[0])?, &map_of(&args[1])?))));
+    m.insert(name, f);
+    let (name, f) = native("value_counts", 1, |args| {
+        let values = records_of(&args[0])?;
+        Ok(Value::List(
+            value_counts(&values)
+                .into_iter()
+                .map(|(value, count)| Value::Tuple(vec![value, Value::Int(count)]))
+                .collect(),
+        ))
+    });
+    m.insert(name, f);
+    m
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(age: i64, name: &str) -> Value {
+        let mut m = ImMap::new();
+        m.insert("age".to_string(), Value::Int(age));
+        m.insert("name".to_string(), Value::String(name.to_string()));
+        Value::Map(m)
+    }
+
+    #[test]
+    fn describe_reports_numeric_mean_and_categorical_counts() {
+        let records = vec![
+            record(30, "alice"),
+            record(40, "bob"),
+            record(50, "alice"),
+        ];
+        let columns = describe(&records);
+        assert_eq!(columns.len(), 2);
+
+        let age_col = columns
+            .iter()
+            .find(|c| matches!(c, Value::Map(m) if m.get("column") == Some(&Value::String("age".to_string()))))
+            .unwrap();
+        let Value::Map(age_col) = age_col else { unreachable!() };
+        assert_eq!(age_col.get("mean"), Some(&Value::Float(40.0)));
+
+        let name_col = columns
+            .iter()
+            .find(|c| matches!(c, Value::Map(m) if m.get("column") == Some(&Value::String("name".to_string()))))
+            .unwrap();
+        let Value::Map(name_col) = name_col else { unreachable!() };
+        assert!(name_col.get("value_counts").is_some());
+    }
+
+    #[test]
+    fn select_keeps_only_the_named_fields() {
+        let mut row = ImMap::new();
+        row.insert("age".to_string(), Value::Int(30));
+        row.insert("name".to_string(), Value::String("alice".to_string()));
+        row.insert("city".to_string(), Value::String("nyc".to_string()));
+        let records = vec![Value::Map(row)];
+
+        let selected = select(&records, &["age".to_string(), "name".to_string()]);
+        let Value::Map(row) = &selected[0] else { unreachable!() };
+        assert_eq!(row.len(), 2);
+        assert_eq!(row.get("age"), Some(&Value::Int(30)));
+        assert!(row.get("city").is_none());
+    }
+
+    fn record_with_age(age: i64) -> Value {
+        let mut m = ImMap::new();
+        m.insert("age".to_string(), Value::Int(age));
+        Value::Map(m)
+    }
+
+    #[test]
+    fn filter_records_keeps_rows_above_an_age_threshold() {
+        let records = vec![record_with_age(10), record_with_age(25), record_with_age(40)];
+        let filtered = filter_records(&records, |r| {
+            let Value::Map(row) = r else { return false };
+            matches!(row.get("age"), Some(Value::Int(age)) if *age > 20)
+        });
+        assert_eq!(filtered.len(), 2);
+    }
+
+    #[test]
+    fn gen_dataset_produces_the_requested_row_count_and_shape() {
+        let constant_int = Value::Dist(Arc::new(crate::random::Distribution {
+            name: "constant_int".to_string(),
+            params: vec![],
+            sampler: Arc::new(|_rng| Value::Int(1)),
+        }));
+
+        let mut schema = ImMap::new();
+        schema.insert("id".to_string(), constant_int);
+        schema.insert("score".to_string(), crate::random::normal(0.0, 1.0).unwrap());
+
+        let rows = gen_dataset(&schema, 100).unwrap();
+        assert_eq!(rows.len(), 100);
+        for row in &rows {
+            let Value::Map(fields) = row else { panic!("expected a record map") };
+            assert!(matches!(fields.get("id"), Some(Value::Int(_))));
+            assert!(matches!(fields.get("score"), Some(Value::Float(_))));
+        }
+    }
+
+    #[test]
+    fn merge_overwrites_shared_keys_with_the_right_hand_map() {
+        let mut a = ImMap::new();
+        a.insert("x".to_string(), Value::Int(1));
+        a.insert("y".to_string(), Value::Int(2));
+        let mut b = ImMap::new();
+        b.insert("y".to_string(), Value::Int(20));
+        b.insert("z".to_string(), Value::Int(3));
+
+        let merged = merge(&a, &b);
+        assert_eq!(merged.get("x"), Some(&Value::Int(1)));
+        assert_eq!(merged.get("y"), Some(&Value::Int(20)));
+        assert_eq!(merged.get("z"), Some(&Value::Int(3)));
+    }
+
+    #[test]
+    fn deep_merge_recurses_into_nested_maps_instead_of_overwriting_them() {
+        let mut a_inner = ImMap::new();
+        a_inner.insert("keep".to_string(), Value::Int(1));
+        a_inner.insert("shared".to_string(), Value::Int(1));
+        let mut a = ImMap::new();
+        a.insert("nested".to_string(), Value::Map(a_inner));
+
+        let mut b_inner = ImMap::new();
+        b_inner.insert("shared".to_string(), Value::Int(2));
+        b_inner.insert("added".to_string(), Value::Int(3));
+        let mut b = ImMap::new();
+        b.insert("nested".to_string(), Value::Map(b_inner));
+
+        let Value::Map(nested) = deep_merge(&a, &b).remove("nested").unwrap() else { panic!("expected a nested map") };
+        assert_eq!(nested.get("keep"), Some(&Value::Int(1)));
+        assert_eq!(nested.get("shared"), Some(&Value::Int(2)));
+        assert_eq!(nested.get("added"), Some(&Value::Int(3)));
+    }
+
+    #[test]
+    fn value_counts_sorts_by_frequency_with_a_deterministic_tiebreak() {
+        let values = vec![
+            Value::String("a".to_string()),
+            Value::String("b".to_string()),
+            Value::String("a".to_string()),
+            Value::String("c".to_string()),
+            Value::String("b".to_string()),
+            Value::String("a".to_string()),
+        ];
+        let counts = value_counts(&values);
+        assert_eq!(
+            counts,
+            vec![
+                (Value::String("a".to_string()), 3),
+                (Value::String("b".to_string()), 2),
+                (Value::String("c".to_string()), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn value_counts_is_not_truncated_unlike_top_value_counts() {
+        let values: Vec<Value> = (0..TOP_K as i64 + 3).map(Value::Int).collect();
+        assert_eq!(value_counts(&values).len(), values.len());
+    }
+
+    fn row(sex: &str, survived: &str) -> Value {
+        let mut m = ImMap::new();
+        m.insert("sex".to_string(), Value::String(sex.to_string()));
+        m.insert("survived".to_string(), Value::String(survived.to_string()));
+        Value::Map(m)
+    }
+
+    #[test]
+    fn crosstab_counts_co_occurrences_of_two_categorical_fields() {
+        let records = vec![
+            row("f", "yes"),
+            row("f", "yes"),
+            row("f", "no"),
+            row("m", "no"),
+            row("m", "no"),
+        ];
+        let table = crosstab(&records, "sex", "survived").unwrap();
+
+        let f_idx = table.row_labels.iter().position(|r| r == "f").unwrap();
+        let m_idx = table.row_labels.iter().position(|r| r == "m").unwrap();
+        let yes_idx = table.col_labels.iter().position(|c| c == "yes").unwrap();
+        let no_idx = table.col_labels.iter().position(|c| c == "no").unwrap();
+
+        assert_eq!(table.counts[f_idx][yes_idx], 2);
+        assert_eq!(table.counts[f_idx][no_idx], 1);
+        assert_eq!(table.counts[m_idx][yes_idx], 0);
+        assert_eq!(table.counts[m_idx][no_idx], 2);
+    }
+}
@@ -0,0 +1,90 @@
+//! A structural, path-annotated diff between two [`Value`]s, so a failed
+//! `assert_eq` (or any other equality check) can report exactly where two
+//! otherwise-similar values diverge instead of dumping both in full.
+
+use crate::value::Value;
+
+/// Returns a human-readable, path-annotated diff between `a` and `b`, or
+/// `None` if they're equal. Recurses into lists/tuples/maps; anything else
+/// that differs is reported at its own path.
+pub fn value_diff(a: &Value, b: &Value) -> Option<String> {
+    let mut diffs = Vec::new();
+    diff_at(a, b, "root", &mut diffs);
+    if diffs.is_empty() {
+        None
+    } else {
+        Some(diffs.join("\n"))
+    }
+}
+
+fn diff_at(a: &Value, b: &Value, path: &str, out: &mut Vec<String>) {
+    match (a, b) {
+        (Value::List(xs), Value::List(ys)) => {
+            diff_seq(xs.iter().collect(), ys.iter().collect(), path, out);
+        }
+        (Value::Tuple(xs), Value::Tuple(ys)) => {
+            diff_seq(xs.iter().collect(), ys.iter().collect(), path, out);
+        }
+        (Value::Map(xs), Value::Map(ys)) => {
+            let mut keys: Vec<&String> = xs.keys().chain(ys.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                match (xs.get(key), ys.get(key)) {
+                    (Some(x), Some(y)) => diff_at(x, y, &format!("{path}.{key}"), out),
+                    (Some(x), None) => out.push(format!("{path}.{key}: removed (was `{x}`)")),
+                    (None, Some(y)) => out.push(format!("{path}.{key}: added `{y}`")),
+                    (None, None) => unreachable!("key came from one of the two maps"),
+                }
+            }
+        }
+        _ if a == b => {}
+        _ => out.push(format!("{path}: `{a}` != `{b}`")),
+    }
+}
+
+fn diff_seq<'a>(xs: Vec<&'a Value>, ys: Vec<&'a Value>, path: &str, out: &mut Vec<String>) {
+    if xs.len() != ys.len() {
+        out.push(format!("{path}: length {} != {}", xs.len(), ys.len()));
+        return;
+    }
+    for (i, (x, y)) in xs.iter().zip(ys.iter()).enumerate() {
+        diff_at(x, y, &format!("{path}[{i}]"), out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use im::HashMap as ImMap;
+
+    #[test]
+    fn equal_values_have_no_diff() {
+        assert_eq!(value_diff(&Value::Int(1), &Value::Int(1)), None);
+    }
+
+    #[test]
+    fn scalar_mismatch_reports_both_values() {
+        let diff = value_diff(&Value::Int(1), &Value::Int(2)).unwrap();
+        assert!(diff.contains('1') && diff.contains('2'));
+    }
+
+    #[test]
+    fn a_list_length_mismatch_is_reported_without_recursing() {
+        let a = Value::List(im::vector![Value::Int(1)]);
+        let b = Value::List(im::vector![Value::Int(1), Value::Int(2)]);
+        let diff = value_diff(&a, &b).unwrap();
+        assert!(diff.contains("length"));
+    }
+
+    #[test]
+    fn a_nested_map_field_difference_reports_its_path() {
+        let inner_a = Value::Map(ImMap::unit("x".to_string(), Value::Int(1)));
+        let inner_b = Value::Map(ImMap::unit("x".to_string(), Value::Int(2)));
+        let a = Value::Map(ImMap::unit("nested".to_string(), inner_a));
+        let b = Value::Map(ImMap::unit("nested".to_string(), inner_b));
+
+        let diff = value_diff(&a, &b).unwrap();
+        assert!(diff.contains("root.nested.x"), "diff was: {diff}");
+    }
+}
@@ -0,0 +1,99 @@
+//! Text encodings for [`Value::Bytes`]: base64 and hex.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine as _;
+
+use crate::value::{NativeFunction, Value};
+
+fn bytes_of(v: &Value) -> Result<Vec<u8>, String> {
+    match v {
+        Value::Bytes(b) => Ok(b.clone()),
+        other => Err(format!("expected Bytes, found {other}")),
+    }
+}
+
+fn string_of(v: &Value) -> Result<String, String> {
+    match v {
+        Value::String(s) => Ok(s.clone()),
+        other => Err(format!("expected a String, found {other}")),
+    }
+}
+
+fn native(name: &str, arity: usize, func: impl Fn(&[Value]) -> Result<Value, String> + Send + Sync + 'static) -> (String, NativeFunction) {
+    (
+        name.to_string(),
+        NativeFunction {
+            name: name.to_string(),
+            arity,
+            func: Arc::new(func),
+        },
+    )
+}
+
+pub fn native_functions() -> HashMap<String, NativeFunction> {
+    let mut m = HashMap::new();
+
+    let (name, f) = native("base64_encode", 1, |args| {
+        let bytes = bytes_of(&args[0])?;
+        Ok(Value::String(BASE64.encode(bytes)))
+    });
+    m.insert(name, f);
+
+    let (name, f) = native("base64_decode", 1, |args| {
+        let text = string_of(&args[0])?;
+        match BASE64.decode(text) {
+            Ok(bytes) => Ok(Value::Bytes(bytes)),
+            Err(e) => Ok(Value::Error(format!("invalid base64: {e}"))),
+        }
+    });
+    m.insert(name, f);
+
+    let (name, f) = native("hex_encode", 1, |args| {
+        let bytes = bytes_of(&args[0])?;
+        Ok(Value::String(hex::encode(bytes)))
+    });
+    m.insert(name, f);
+
+    let (name, f) = native("hex_decode", 1, |args| {
+        let text = string_of(&args[0])?;
+        match hex::decode(text) {
+            Ok(bytes) => Ok(Value::Bytes(bytes)),
+            Err(e) => Ok(Value::Error(format!("invalid hex: {e}"))),
+        }
+    });
+    m.insert(name, f);
+
+    m
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base64_round_trips_arbitrary_bytes() {
+        let bytes = vec![0u8, 1, 2, 250, 255];
+        let encoded = BASE64.encode(&bytes);
+        let decoded = BASE64.decode(encoded).unwrap();
+        assert_eq!(decoded, bytes);
+    }
+
+    #[test]
+    fn hex_round_trips_arbitrary_bytes() {
+        let bytes = vec![0u8, 1, 2, 250, 255];
+        let encoded = hex::encode(&bytes);
+        let decoded = hex::decode(encoded).unwrap();
+        assert_eq!(decoded, bytes);
+    }
+
+    #[test]
+    fn invalid_hex_decode_yields_a_value_error() {
+        let funcs = native_functions();
+        let hex_decode = funcs.get("hex_decode").unwrap();
+        let result = (hex_decode.func)(&[Value::String("not hex!".to_string())]).unwrap();
+        assert!(matches!(result, Value::Error(_)));
+    }
+}
@@ -0,0 +1,13 @@
+use thiserror::Error;
+
+/// Errors from the binary/text serialization formats in [`crate::msgpack`]
+/// and [`crate::csv`].
+#[derive(Debug, Clone, Error, PartialEq)]
+pub enum SerialError {
+    #[error("failed to decode: {0}")]
+    Decode(String),
+    #[error("failed to encode: {0}")]
+    Encode(String),
+}
+
+pub type SerialResult<T> = Result<T, SerialError>;
@@ -0,0 +1,245 @@
+//! Parameter estimation for a handful of common distribution families, by
+//! maximum likelihood (normal, exponential) or method of moments (beta).
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use im::{HashMap as ImMap, Vector as ImVector};
+
+use crate::value::{NativeFunction, Value};
+
+fn mean(data: &[f64]) -> f64 {
+    data.iter().sum::<f64>() / data.len() as f64
+}
+
+fn variance(data: &[f64], mean: f64) -> f64 {
+    data.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / data.len() as f64
+}
+
+/// MLE for a normal distribution: the sample mean and (population)
+/// standard deviation.
+pub fn fit_normal(data: &[f64]) -> Result<ImMap<String, Value>, String> {
+    if data.is_empty() {
+        return Err("cannot fit a normal distribution to no data".to_string());
+    }
+    let m = mean(data);
+    let std = variance(data, m).sqrt();
+    let mut out = ImMap::new();
+    out.insert("mean".to_string(), Value::Float(m));
+    out.insert("std".to_string(), Value::Float(std));
+    Ok(out)
+}
+
+/// MLE for an exponential distribution: `rate = 1 / mean`. Errors on
+/// non-positive data, which an exponential can never have generated.
+pub fn fit_exponential(data: &[f64]) -> Result<ImMap<String, Value>, String> {
+    if data.is_empty() {
+        return Err("cannot fit an exponential distribution to no data".to_string());
+    }
+    if data.iter().any(|&x| x <= 0.0) {
+        return Err("exponential data must be strictly positive".to_string());
+    }
+    let m = mean(data);
+    let mut out = ImMap::new();
+    out.insert("rate".to_string(), Value::Float(1.0 / m));
+    Ok(out)
+}
+
+/// Method-of-moments fit for a Beta(alpha, beta) distribution over data in
+/// `(0, 1)`. Errors on degenerate data (zero variance, or values outside
+/// the unit interval).
+pub fn fit_beta(data: &[f64]) -> Result<ImMap<String, Value>, String> {
+    if data.is_empty() {
+        return Err("cannot fit a beta distribution to no data".to_string());
+    }
+    if data.iter().any(|&x| x <= 0.0 || x >= 1.0) {
+        return Err("beta data must lie in (0, 1)".to_string());
+    }
+    let m = mean(data);
+    let v = variance(data, m);
+    if v <= 0.0 {
+        return Err("beta fit requires data with nonzero variance".to_string());
+    }
+    let common = m * (1.0 - m) / v - 1.0;
+    if common <= 0.0 {
+        return Err("data is too dispersed to admit a beta fit".to_string());
+    }
+    let alpha = m * common;
+    let beta = (1.0 - m) * common;
+    let mut out = ImMap::new();
+    out.insert("alpha".to_string(), Value::Float(alpha));
+    out.insert("beta".to_string(), Value::Float(beta));
+    Ok(out)
+}
+
+/// Akaike information criterion: lower is better. `k` is the number of
+/// fitted parameters.
+pub fn aic(log_likelihood: f64, k: f64) -> f64 {
+    2.0 * k - 2.0 * log_likelihood
+}
+
+/// Bayesian information criterion: lower is better, and penalizes
+/// parameter count more heavily than AIC as `n` grows.
+pub fn bic(log_likelihood: f64, k: f64, n: f64) -> f64 {
+    k * n.ln() - 2.0 * log_likelihood
+}
+
+fn normal_log_likelihood(data: &[f64], mean: f64, std: f64) -> f64 {
+    data.iter()
+        .map(|x| -0.5 * (2.0 * std::f64::consts::PI * std * std).ln() - (x - mean).powi(2) / (2.0 * std * std))
+        .sum()
+}
+
+fn exponential_log_likelihood(data: &[f64], rate: f64) -> f64 {
+    data.iter().map(|x| rate.ln() - rate * x).sum()
+}
+
+fn float_param(params: &ImMap<String, Value>, key: &str) -> f64 {
+    match params.get(key) {
+        Some(Value::Float(x)) => *x,
+        _ => unreachable!("fit_* always populates {key} as a Float"),
+    }
+}
+
+/// One candidate in a [`compare_fits`] ranking.
+pub struct FitComparison {
+    pub family: String,
+    pub params: ImMap<String, Value>,
+    pub aic: f64,
+    pub bic: f64,
+}
+
+/// Fits `normal` and `exponential` to `data` and ranks them by AIC
+/// (best first). A family that can't be fit to `data` (e.g. exponential
+/// over data with negative values) is simply omitted from the ranking.
+pub fn compare_fits(data: &[f64]) -> Vec<FitComparison> {
+    let n = data.len() as f64;
+    let mut results = Vec::new();
+
+    if let Ok(params) = fit_normal(data) {
+        let ll = normal_log_likelihood(data, float_param(&params, "mean"), float_param(&params, "std"));
+        results.push(FitComparison { family: "normal".to_string(), aic: aic(ll, 2.0), bic: bic(ll, 2.0, n), params });
+    }
+    if let Ok(params) = fit_exponential(data) {
+        let ll = exponential_log_likelihood(data, float_param(&params, "rate"));
+        results.push(FitComparison { family: "exponential".to_string(), aic: aic(ll, 1.0), bic: bic(ll, 1.0, n), params });
+    }
+
+    results.sort_by(|a, b| a.aic.partial_cmp(&b.aic).unwrap());
+    results
+}
+
+fn as_float(value: &Value) -> Result<f64, String> {
+    match value {
+        Value::Float(x) => Ok(*x),
+        Value::Int(i) => Ok(*i as f64),
+        other => Err(format!("expected a number, found {other}")),
+    }
+}
+
+fn as_floats(values: &Value) -> Result<Vec<f64>, String> {
+    match values {
+        Value::List(items) => items
+            .iter()
+            .map(|v| match v {
+                Value::Float(x) => Ok(*x),
+                Value::Int(i) => Ok(*i as f64),
+                other => Err(format!("expected a numeric sample, found {other}")),
+            })
+            .collect(),
+        other => Err(format!("expected a list of samples, found {other}")),
+    }
+}
+
+fn native(name: &str, arity: usize, func: impl Fn(&[Value]) -> Result<Value, String> + Send + Sync + 'static) -> (String, NativeFunction) {
+    (
+        name.to_string(),
+        NativeFunction {
+            name: name.to_string(),
+            arity,
+            func: Arc::new(func),
+        },
+    )
+}
+
+pub fn native_functions() -> HashMap<String, NativeFunction> {
+    let mut m = HashMap::new();
+    let (name, f) = native("fit_normal", 1, |args| fit_normal(&as_floats(&args[0])?).map(Value::Map));
+    m.insert(name, f);
+    let (name, f) = native("fit_exponential", 1, |args| fit_exponential(&as_floats(&args[0])?).map(Value::Map));
+    m.insert(name, f);
+    let (name, f) = native("fit_beta", 1, |args| fit_beta(&as_floats(&args[0])?).map(Value::Map));
+    m.insert(name, f);
+    let (name, f) = native("aic", 2, |args| {
+        let ll = as_float(&args[0])?;
+        let k = as_float(&args[1])?;
+        Ok(Value::Float(aic(ll, k)))
+    });
+    m.insert(name, f);
+    let (name, f) = native("bic", 3, |args| {
+        let ll = as_float(&args[0])?;
+        let k = as_float(&args[1])?;
+        let n = as_float(&args[2])?;
+        Ok(Value::Float(bic(ll, k, n)))
+    });
+    m.insert(name, f);
+    let (name, f) = native("compare_fits", 1, |args| {
+        let data = as_floats(&args[0])?;
+        let ranking: ImVector<Value> = compare_fits(&data)
+            .into_iter()
+            .map(|fit| {
+                let mut row = ImMap::new();
+                row.insert("family".to_string(), Value::String(fit.family));
+                row.insert("aic".to_string(), Value::Float(fit.aic));
+                row.insert("bic".to_string(), Value::Float(fit.bic));
+                row.insert("params".to_string(), Value::Map(fit.params));
+                Value::Map(row)
+            })
+            .collect();
+        Ok(Value::List(ranking))
+    });
+    m.insert(name, f);
+    m
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::normal;
+
+    #[test]
+    fn fitting_a_normal_recovers_parameters_close_to_the_truth() {
+        let Value::Dist(dist) = normal(5.0, 2.0).unwrap() else { unreachable!() };
+        let samples: Vec<f64> = (0..5000)
+            .map(|_| match dist.sample() {
+                Value::Float(x) => x,
+                _ => unreachable!(),
+            })
+            .collect();
+
+        let fitted = fit_normal(&samples).unwrap();
+        let Some(Value::Float(mean)) = fitted.get("mean") else { panic!("missing mean") };
+        let Some(Value::Float(std)) = fitted.get("std") else { panic!("missing std") };
+        assert!((mean - 5.0).abs() < 0.2, "fitted mean {mean} far from 5.0");
+        assert!((std - 2.0).abs() < 0.2, "fitted std {std} far from 2.0");
+    }
+
+    #[test]
+    fn fit_exponential_rejects_non_positive_data() {
+        assert!(fit_exponential(&[1.0, -1.0, 2.0]).is_err());
+    }
+
+    #[test]
+    fn comparing_fits_on_normal_data_prefers_normal_over_exponential() {
+        let Value::Dist(dist) = normal(10.0, 1.0).unwrap() else { unreachable!() };
+        let samples: Vec<f64> = (0..2000)
+            .map(|_| match dist.sample() {
+                Value::Float(x) => x,
+                _ => unreachable!(),
+            })
+            .collect();
+
+        let ranking = compare_fits(&samples);
+        assert_eq!(ranking[0].family, "normal");
+    }
+}
@@ -0,0 +1,88 @@
+//! UUID/ULID generation natives, with seeded reproducibility for tests and
+//! synthetic datasets.
+//!
+//! This is a stopgap session seed, ahead of a proper per-thread RNG context
+//! this should eventually share with the rest of the runtime's RNG use.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use uuid::Builder as UuidBuilder;
+
+use crate::value::{NativeFunction, Value};
+
+thread_local! {
+    static SESSION_RNG: RefCell<Option<StdRng>> = const { RefCell::new(None) };
+}
+
+/// Seeds this thread's id generation so `uuid_v4`/`ulid` become
+/// reproducible. Intended for tests and synthetic-data generation.
+pub fn set_seed(seed: u64) {
+    SESSION_RNG.with(|rng| *rng.borrow_mut() = Some(StdRng::seed_from_u64(seed)));
+}
+
+fn with_rng<T>(f: impl FnOnce(&mut dyn rand::RngCore) -> T) -> T {
+    SESSION_RNG.with(|rng| match rng.borrow_mut().as_mut() {
+        Some(r) => f(r),
+        None => f(&mut rand::thread_rng()),
+    })
+}
+
+/// A random (v4) UUID, rendered as its standard hyphenated string form.
+pub fn uuid_v4() -> String {
+    let bytes: [u8; 16] = with_rng(|rng| rng.gen());
+    UuidBuilder::from_random_bytes(bytes).into_uuid().to_string()
+}
+
+/// A ULID: a sortable, timestamp-prefixed id. The random component is
+/// seeded like [`uuid_v4`]; the timestamp component is the current time.
+pub fn ulid() -> String {
+    let millis = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .expect("system clock is before the Unix epoch")
+        .as_millis() as u64;
+    let randomness: u128 = with_rng(|rng| rng.gen());
+    ulid::Ulid::from_parts(millis, randomness).to_string()
+}
+
+fn native(name: &str, arity: usize, func: impl Fn(&[Value]) -> Result<Value, String> + Send + Sync + 'static) -> (String, NativeFunction) {
+    (
+        name.to_string(),
+        NativeFunction {
+            name: name.to_string(),
+            arity,
+            func: Arc::new(func),
+        },
+    )
+}
+
+pub fn native_functions() -> HashMap<String, NativeFunction> {
+    let mut m = HashMap::new();
+    let (name, f) = native("uuid_v4", 0, |_args| Ok(Value::String(uuid_v4())));
+    m.insert(name, f);
+    let (name, f) = native("ulid", 0, |_args| Ok(Value::String(ulid())));
+    m.insert(name, f);
+    m
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn two_v4_uuids_differ() {
+        assert_ne!(uuid_v4(), uuid_v4());
+    }
+
+    #[test]
+    fn seeded_generation_reproduces_the_same_uuid() {
+        set_seed(42);
+        let first = uuid_v4();
+        set_seed(42);
+        let second = uuid_v4();
+        assert_eq!(first, second);
+    }
+}
@@ -0,0 +1,156 @@
+//! Streaming file reading: chunked readers that avoid loading an entire file
+//! into memory at once, for datasets too large to parse as a single
+//! `Value::List` (unlike [`crate::csv::stringify`]'s in-memory writer side).
+
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use im::HashMap as ImMap;
+use thiserror::Error;
+
+use crate::csv::parse_row;
+use crate::value::Value;
+
+/// Errors opening or reading a streamed file.
+#[derive(Debug, Error)]
+pub enum IoError {
+    #[error("I/O error reading {path}: {source}")]
+    Read { path: String, #[source] source: std::io::Error },
+}
+
+pub type IoResult<T> = Result<T, IoError>;
+
+/// Reads a CSV file `chunk_size` records at a time rather than parsing the
+/// whole file up front, so a file much larger than memory can still be
+/// processed incrementally. The first line is read as the header and fixes
+/// each record's columns; a field equal to `null_token` becomes
+/// `Value::Unit`, everything else a `Value::String` (callers needing
+/// numeric types coerce downstream, same as elsewhere in the runtime).
+/// Quoted fields may contain commas, per [`parse_row`], but not embedded
+/// newlines, since each record must be exactly one line.
+#[derive(Debug)]
+pub struct CsvChunks {
+    reader: BufReader<File>,
+    path: String,
+    columns: Vec<String>,
+    chunk_size: usize,
+    null_token: String,
+    done: bool,
+}
+
+impl CsvChunks {
+    /// Opens `path` and reads its header row. `chunk_size` is clamped to at
+    /// least 1. [`IoError`] if the file can't be opened or its header line
+    /// can't be read.
+    pub fn open(path: impl AsRef<Path>, chunk_size: usize, null_token: impl Into<String>) -> IoResult<CsvChunks> {
+        let path_str = path.as_ref().display().to_string();
+        let file = File::open(&path).map_err(|source| IoError::Read { path: path_str.clone(), source })?;
+        let mut reader = BufReader::new(file);
+
+        let mut header = String::new();
+        reader.read_line(&mut header).map_err(|source| IoError::Read { path: path_str.clone(), source })?;
+        let columns = parse_row(header.trim_end_matches(['\n', '\r']));
+
+        Ok(CsvChunks { reader, path: path_str, columns, chunk_size: chunk_size.max(1), null_token: null_token.into(), done: false })
+    }
+
+    fn read_record(&mut self) -> IoResult<Option<Value>> {
+        loop {
+            let mut line = String::new();
+            let bytes_read = self.reader.read_line(&mut line).map_err(|source| IoError::Read { path: self.path.clone(), source })?;
+            if bytes_read == 0 {
+                self.done = true;
+                return Ok(None);
+            }
+            let line = line.trim_end_matches(['\n', '\r']);
+            if line.is_empty() {
+                continue;
+            }
+
+            let mut fields = ImMap::new();
+            for (column, field) in self.columns.iter().zip(parse_row(line)) {
+                let value = if field == self.null_token { Value::Unit } else { Value::String(field) };
+                fields.insert(column.clone(), value);
+            }
+            return Ok(Some(Value::Map(fields)));
+        }
+    }
+}
+
+impl Iterator for CsvChunks {
+    /// One chunk of up to `chunk_size` records, or an error from the first
+    /// record read that failed.
+    type Item = IoResult<Vec<Value>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let mut chunk = Vec::with_capacity(self.chunk_size);
+        for _ in 0..self.chunk_size {
+            match self.read_record() {
+                Ok(Some(record)) => chunk.push(record),
+                Ok(None) => break,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+        if chunk.is_empty() {
+            None
+        } else {
+            Some(Ok(chunk))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn write_csv(contents: &str) -> tempfile::NamedTempFile {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(contents.as_bytes()).unwrap();
+        file
+    }
+
+    #[test]
+    fn chunks_split_records_into_groups_of_the_requested_size() {
+        let file = write_csv("a,b\n1,2\n3,4\n5,6\n7,8\n9,10\n");
+        let chunks: Vec<Vec<Value>> = CsvChunks::open(file.path(), 2, "").unwrap().map(Result::unwrap).collect();
+        assert_eq!(chunks.len(), 3);
+        assert_eq!(chunks[0].len(), 2);
+        assert_eq!(chunks[1].len(), 2);
+        assert_eq!(chunks[2].len(), 1);
+    }
+
+    #[test]
+    fn every_record_keeps_its_header_derived_columns() {
+        let file = write_csv("name,age\nbob,30\nalice,25\n");
+        let records: Vec<Value> = CsvChunks::open(file.path(), 10, "").unwrap().flat_map(Result::unwrap).collect();
+        let Value::Map(first) = &records[0] else { panic!("expected a record map") };
+        assert_eq!(first.get("name"), Some(&Value::String("bob".to_string())));
+        assert_eq!(first.get("age"), Some(&Value::String("30".to_string())));
+    }
+
+    #[test]
+    fn a_field_matching_the_null_token_becomes_unit() {
+        let file = write_csv("a,b\n1,NA\n");
+        let records: Vec<Value> = CsvChunks::open(file.path(), 10, "NA").unwrap().flat_map(Result::unwrap).collect();
+        let Value::Map(fields) = &records[0] else { panic!("expected a record map") };
+        assert_eq!(fields.get("b"), Some(&Value::Unit));
+    }
+
+    #[test]
+    fn opening_a_missing_file_is_an_io_error() {
+        let err = CsvChunks::open("/no/such/file.csv", 10, "").unwrap_err();
+        assert!(matches!(err, IoError::Read { .. }));
+    }
+
+    #[test]
+    fn blank_lines_between_records_are_skipped() {
+        let file = write_csv("a,b\n1,2\n\n3,4\n");
+        let records: Vec<Value> = CsvChunks::open(file.path(), 10, "").unwrap().flat_map(Result::unwrap).collect();
+        assert_eq!(records.len(), 2);
+    }
+}
@@ -0,0 +1,114 @@
+//! JSON conversion for [`Value`].
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use im::HashMap as ImMap;
+use serde_json::Value as JsonValue;
+
+use crate::value::{sorted_map_entries, NativeFunction, Value};
+
+/// Converts a parsed JSON value into a betlang [`Value`]. Numbers try
+/// `i64` first, then fall back to `u64` (preserving ids above
+/// `i64::MAX` exactly as [`Value::UInt`] instead of a lossy float), and
+/// only use `f64` for values that are actually fractional or too large
+/// for either integer type.
+pub fn json_to_value(json: &JsonValue) -> Value {
+    match json {
+        JsonValue::Null => Value::Unit,
+        JsonValue::Bool(b) => Value::Bool(*b),
+        JsonValue::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                Value::Int(i)
+            } else if let Some(u) = n.as_u64() {
+                Value::UInt(u)
+            } else {
+                Value::Float(n.as_f64().unwrap_or(f64::NAN))
+            }
+        }
+        JsonValue::String(s) => Value::String(s.clone()),
+        JsonValue::Array(items) => Value::List(items.iter().map(json_to_value).collect()),
+        JsonValue::Object(fields) => {
+            let mut m = ImMap::new();
+            for (key, value) in fields {
+                m.insert(key.clone(), json_to_value(value));
+            }
+            Value::Map(m)
+        }
+    }
+}
+
+/// Converts a betlang [`Value`] to JSON. Variants with no natural JSON
+/// shape (distributions, native functions, open files, errors) fall back
+/// to their `Display` form as a string, since this path exists for human
+/// inspection, not for round-tripping those variants.
+pub fn value_to_json(value: &Value) -> JsonValue {
+    match value {
+        Value::Unit => JsonValue::Null,
+        Value::Bool(b) => JsonValue::Bool(*b),
+        Value::Int(i) => JsonValue::Number((*i).into()),
+        Value::UInt(u) => JsonValue::Number((*u).into()),
+        Value::Float(x) => serde_json::Number::from_f64(*x).map(JsonValue::Number).unwrap_or(JsonValue::Null),
+        Value::String(s) => JsonValue::String(s.clone()),
+        Value::List(items) => JsonValue::Array(items.iter().map(value_to_json).collect()),
+        Value::Tuple(items) => JsonValue::Array(items.iter().map(value_to_json).collect()),
+        Value::Set(items) => JsonValue::Array(items.keys().map(value_to_json).collect()),
+        Value::Map(fields) => {
+            let mut m = serde_json::Map::new();
+            for (key, value) in sorted_map_entries(fields) {
+                m.insert(key.clone(), value_to_json(value));
+            }
+            JsonValue::Object(m)
+        }
+        other => JsonValue::String(other.to_string()),
+    }
+}
+
+/// Renders a betlang [`Value`] as pretty-printed JSON, via [`value_to_json`].
+pub fn to_string_pretty(value: &Value) -> String {
+    serde_json::to_string_pretty(&value_to_json(value)).expect("Value -> JSON conversion never produces unserializable data")
+}
+
+/// Renders a betlang [`Value`] as a single-line JSON string, via [`value_to_json`].
+pub fn encode(value: &Value) -> String {
+    serde_json::to_string(&value_to_json(value)).expect("Value -> JSON conversion never produces unserializable data")
+}
+
+fn native(name: &str, arity: usize, func: impl Fn(&[Value]) -> Result<Value, String> + Send + Sync + 'static) -> (String, NativeFunction) {
+    (
+        name.to_string(),
+        NativeFunction {
+            name: name.to_string(),
+            arity,
+            func: Arc::new(func),
+        },
+    )
+}
+
+pub fn native_functions() -> HashMap<String, NativeFunction> {
+    let mut m = HashMap::new();
+    let (name, f) = native("json_encode", 1, |args| Ok(Value::String(encode(&args[0]))));
+    m.insert(name, f);
+    m
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn large_u64_survives_the_round_trip() {
+        let json: JsonValue = serde_json::from_str("18446744073709551615").unwrap();
+        assert_eq!(json_to_value(&json), Value::UInt(u64::MAX));
+    }
+
+    #[test]
+    fn json_encode_renders_a_record_as_a_single_line_object() {
+        let funcs = native_functions();
+        let encode = funcs.get("json_encode").unwrap();
+        let mut fields = ImMap::new();
+        fields.insert("x".to_string(), Value::Int(1));
+        let result = (encode.func)(&[Value::Map(fields)]).unwrap();
+        assert_eq!(result, Value::String("{\"x\":1}".to_string()));
+    }
+}
@@ -0,0 +1,25 @@
+//! `bet-rt` is the betlang runtime: the value representation, native
+//! distributions and functions, and serialization and I/O
+//! helpers that the interpreter and CLI build on.
+
+pub mod assert;
+pub mod collections;
+pub mod csv;
+pub mod data;
+pub mod diff;
+pub mod encoding;
+pub mod error;
+pub mod fit;
+pub mod hashing;
+pub mod id;
+pub mod io;
+pub mod json;
+pub mod msgpack;
+pub mod pretty;
+pub mod random;
+pub mod snapshot;
+pub mod stats;
+pub mod strings;
+pub mod value;
+
+pub use value::{NativeFunction, Value};
@@ -0,0 +1,124 @@
+//! MessagePack conversion for [`Value`], plus a debugging bridge to JSON.
+
+use im::HashMap as ImMap;
+use rmpv::Value as MsgValue;
+
+use crate::error::{SerialError, SerialResult};
+use crate::json;
+use crate::value::{sorted_map_entries, Value};
+
+fn msgpack_to_value(msg: &MsgValue) -> Value {
+    match msg {
+        MsgValue::Nil => Value::Unit,
+        MsgValue::Boolean(b) => Value::Bool(*b),
+        MsgValue::Integer(i) => match i.as_i64() {
+            Some(i) => Value::Int(i),
+            None => Value::UInt(i.as_u64().unwrap_or(0)),
+        },
+        MsgValue::F32(x) => Value::Float(*x as f64),
+        MsgValue::F64(x) => Value::Float(*x),
+        MsgValue::String(s) => Value::String(s.as_str().unwrap_or_default().to_string()),
+        MsgValue::Binary(b) => Value::Bytes(b.clone()),
+        MsgValue::Array(items) => Value::List(items.iter().map(msgpack_to_value).collect()),
+        MsgValue::Map(pairs) => {
+            let mut m = ImMap::new();
+            for (key, value) in pairs {
+                let key = key.as_str().map(|s| s.to_string()).unwrap_or_else(|| key.to_string());
+                m.insert(key, msgpack_to_value(value));
+            }
+            Value::Map(m)
+        }
+        MsgValue::Ext(_, _) => Value::Error("unsupported msgpack extension type".to_string()),
+    }
+}
+
+fn value_to_msgpack(value: &Value) -> MsgValue {
+    match value {
+        Value::Unit => MsgValue::Nil,
+        Value::Bool(b) => MsgValue::Boolean(*b),
+        Value::Int(i) => MsgValue::Integer((*i).into()),
+        Value::UInt(u) => MsgValue::Integer((*u).into()),
+        Value::Float(x) => MsgValue::F64(*x),
+        Value::String(s) => MsgValue::String(s.clone().into()),
+        Value::List(items) => MsgValue::Array(items.iter().map(value_to_msgpack).collect()),
+        Value::Tuple(items) => MsgValue::Array(items.iter().map(value_to_msgpack).collect()),
+        Value::Set(items) => MsgValue::Array(items.keys().map(value_to_msgpack).collect()),
+        Value::Map(fields) => MsgValue::Map(
+            sorted_map_entries(fields)
+                .into_iter()
+                .map(|(k, v)| (MsgValue::String(k.clone().into()), value_to_msgpack(v)))
+                .collect(),
+        ),
+        Value::Bytes(b) => MsgValue::Binary(b.clone()),
+        other => MsgValue::String(other.to_string().into()),
+    }
+}
+
+/// Decodes a MessagePack-encoded byte slice into a [`Value`].
+pub fn from_bytes(bytes: &[u8]) -> SerialResult<Value> {
+    let mut cursor = bytes;
+    let msg = rmpv::decode::read_value(&mut cursor).map_err(|e| SerialError::Decode(e.to_string()))?;
+    Ok(msgpack_to_value(&msg))
+}
+
+/// Encodes a [`Value`] as MessagePack bytes.
+pub fn to_bytes(value: &Value) -> SerialResult<Vec<u8>> {
+    let msg = value_to_msgpack(value);
+    let mut buf = Vec::new();
+    rmpv::encode::write_value(&mut buf, &msg).map_err(|e| SerialError::Encode(e.to_string()))?;
+    Ok(buf)
+}
+
+/// Decodes MessagePack bytes and re-encodes them as pretty JSON, so a
+/// binary payload can be inspected without writing a betlang program.
+/// Just `from_bytes` composed with [`json::to_string_pretty`].
+pub fn to_json_string(bytes: &[u8]) -> SerialResult<String> {
+    let value = from_bytes(bytes)?;
+    Ok(json::to_string_pretty(&value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nested_sample() -> Value {
+        let mut inner = ImMap::new();
+        inner.insert("id".to_string(), Value::UInt(18_446_744_073_709_551_615));
+        inner.insert("name".to_string(), Value::String("alice".to_string()));
+        let mut outer = ImMap::new();
+        outer.insert("user".to_string(), Value::Map(inner));
+        outer.insert("tags".to_string(), Value::List(vec![Value::String("a".to_string()), Value::String("b".to_string())].into()));
+        Value::Map(outer)
+    }
+
+    #[test]
+    fn encoding_a_map_is_deterministic_regardless_of_insertion_order() {
+        let mut forward = ImMap::new();
+        forward.insert("b".to_string(), Value::Int(2));
+        forward.insert("a".to_string(), Value::Int(1));
+
+        let mut reverse = ImMap::new();
+        reverse.insert("a".to_string(), Value::Int(1));
+        reverse.insert("b".to_string(), Value::Int(2));
+
+        assert_eq!(to_bytes(&Value::Map(forward)).unwrap(), to_bytes(&Value::Map(reverse)).unwrap());
+    }
+
+    #[test]
+    fn msgpack_round_trip_preserves_nested_structure() {
+        let value = nested_sample();
+        let bytes = to_bytes(&value).unwrap();
+        let decoded = from_bytes(&bytes).unwrap();
+        assert_eq!(value, decoded);
+    }
+
+    #[test]
+    fn to_json_string_pretty_prints_a_decoded_payload() {
+        let value = nested_sample();
+        let bytes = to_bytes(&value).unwrap();
+        let json = to_json_string(&bytes).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed["user"]["name"], "alice");
+        assert_eq!(parsed["tags"][0], "a");
+    }
+}
@@ -0,0 +1,89 @@
+//! A configurable-width pretty-printer for [`Value`], for REPL and `bet run`
+//! output where the single-line [`std::fmt::Display`] form can run off the
+//! edge of a narrow terminal.
+
+use crate::value::{sorted_map_entries, Value};
+
+/// Renders `value` using [`Display`][std::fmt::Display] when it fits within
+/// `width` columns, or wraps and indents lists/maps/sets/tuples
+/// (Wadler-style: try one line, fall back to one item per line) when it
+/// doesn't.
+pub fn pretty_print(value: &Value, width: usize) -> String {
+    pretty_at(value, width, 0)
+}
+
+fn is_container(value: &Value) -> bool {
+    matches!(value, Value::List(_) | Value::Map(_) | Value::Set(_) | Value::Tuple(_))
+}
+
+fn pretty_at(value: &Value, width: usize, indent: usize) -> String {
+    let flat = value.to_string();
+    if !is_container(value) || indent + flat.len() <= width {
+        return flat;
+    }
+
+    let (open, close, items): (char, char, Vec<String>) = match value {
+        Value::List(items) => ('[', ']', items.iter().map(|v| pretty_at(v, width, indent + 2)).collect()),
+        Value::Tuple(items) => ('(', ')', items.iter().map(|v| pretty_at(v, width, indent + 2)).collect()),
+        Value::Set(s) => ('{', '}', s.iter().map(|(v, _)| pretty_at(v, width, indent + 2)).collect()),
+        Value::Map(m) => (
+            '{',
+            '}',
+            sorted_map_entries(m)
+                .into_iter()
+                .map(|(k, v)| format!("{k}: {}", pretty_at(v, width, indent + 2 + k.len() + 2)))
+                .collect(),
+        ),
+        _ => unreachable!("is_container guards every other variant"),
+    };
+
+    let pad = " ".repeat(indent + 2);
+    let mut out = String::new();
+    out.push(open);
+    out.push('\n');
+    for (i, item) in items.iter().enumerate() {
+        out.push_str(&pad);
+        out.push_str(item);
+        if i + 1 < items.len() {
+            out.push(',');
+        }
+        out.push('\n');
+    }
+    out.push_str(&" ".repeat(indent));
+    out.push(close);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use im::{HashMap as ImMap, Vector as ImVector};
+
+    #[test]
+    fn a_short_value_prints_on_one_line() {
+        let value = Value::List(im::vector![Value::Int(1), Value::Int(2)]);
+        assert_eq!(pretty_print(&value, 80), "[1, 2]");
+    }
+
+    #[test]
+    fn a_deeply_nested_map_wraps_across_multiple_indented_lines() {
+        let inner = Value::Map(ImMap::unit("b".to_string(), Value::List(ImVector::from(vec![
+            Value::Int(1),
+            Value::Int(2),
+            Value::Int(3),
+            Value::Int(4),
+            Value::Int(5),
+        ]))));
+        let outer = Value::Map(ImMap::unit("a".to_string(), inner));
+
+        let pretty = pretty_print(&outer, 10);
+        let lines: Vec<&str> = pretty.lines().collect();
+        assert!(lines.len() > 1, "expected multiple lines, got:\n{pretty}");
+        assert!(lines.iter().any(|l| l.starts_with("    ")), "expected nested indentation, got:\n{pretty}");
+    }
+
+    #[test]
+    fn a_scalar_ignores_the_width_budget() {
+        assert_eq!(pretty_print(&Value::Int(123456789), 2), "123456789");
+    }
+}
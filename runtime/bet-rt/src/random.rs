@@ -0,0 +1,948 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use std::cell::RefCell;
+
+use rand::distributions::{Distribution as _, WeightedIndex};
+use rand::{Rng, RngCore};
+use rand_distr::StandardNormal;
+use rand_pcg::Pcg64;
+
+use crate::value::{NativeFunction, Value};
+
+/// Builds a reproducible `Pcg64` from a fixed seed, so scripts that need
+/// deterministic sample sequences (testing, debugging a model) don't have
+/// to depend on `rand_pcg` directly.
+pub fn seeded_rng(seed: u64) -> Pcg64 {
+    use rand::SeedableRng;
+    Pcg64::seed_from_u64(seed)
+}
+
+thread_local! {
+    static GLOBAL_RNG: RefCell<Option<Pcg64>> = const { RefCell::new(None) };
+}
+
+/// Seeds this thread's global RNG, so every [`Distribution::sample`] call
+/// (which has no explicit RNG to plug a seed into) becomes reproducible —
+/// the runtime-wide analog of the CLI's `--seed` flag. Persists until the
+/// next call or the thread ends.
+pub fn set_global_seed(seed: u64) {
+    GLOBAL_RNG.with(|rng| *rng.borrow_mut() = Some(seeded_rng(seed)));
+}
+
+/// Runs `f` with this thread's global RNG seeded to `seed` for the
+/// duration of the call, restoring whatever was set before it returns.
+/// Prefer this over [`set_global_seed`] in tests, so seeding doesn't leak
+/// into unrelated tests that happen to share a thread.
+pub fn with_global_rng<T>(seed: u64, f: impl FnOnce() -> T) -> T {
+    let previous = GLOBAL_RNG.with(|rng| rng.borrow_mut().replace(seeded_rng(seed)));
+    let result = f();
+    GLOBAL_RNG.with(|rng| *rng.borrow_mut() = previous);
+    result
+}
+
+/// Runs `f` against this thread's global RNG if one has been seeded, or
+/// `rand::thread_rng()` otherwise.
+fn with_global_or_thread_rng<T>(f: impl FnOnce(&mut dyn RngCore) -> T) -> T {
+    GLOBAL_RNG.with(|rng| match rng.borrow_mut().as_mut() {
+        Some(r) => f(r),
+        None => f(&mut rand::thread_rng()),
+    })
+}
+
+/// The SplitMix64 mixing function: derives a well-distributed output from
+/// an input seed. Used to split one seed into many independent-looking
+/// child seeds for [`worker_seed`].
+fn splitmix64(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9E3779B97F4A7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
+
+/// Derives worker `index`'s child seed from a `global_seed`, via SplitMix64
+/// seed-splitting, so parallel workers get independent, reproducible seeds
+/// without handing out consecutive integers (which correlate badly for
+/// some RNGs). Deterministic for a fixed `(global_seed, index)` pair.
+pub fn worker_seed(global_seed: u64, index: u64) -> u64 {
+    splitmix64(global_seed.wrapping_add(splitmix64(index)))
+}
+
+/// Draws `n` samples from `dist` across `worker_count` threads, splitting
+/// the work as evenly as possible. Each worker's RNG is seeded
+/// deterministically from `seed` and its index via [`worker_seed`], so two
+/// runs with the same `seed` and `worker_count` produce bit-identical
+/// results.
+///
+/// The *partitioning* of work across workers — and therefore the exact
+/// sample sequence — depends on `worker_count`: changing it changes how
+/// many samples each worker (and therefore each child seed) draws, so
+/// results are reproducible for a fixed worker count but not comparable
+/// across different ones.
+pub fn parallel_sample(dist: Arc<Distribution>, n: usize, seed: u64, worker_count: usize) -> Vec<Value> {
+    let worker_count = worker_count.max(1);
+    let handles: Vec<_> = (0..worker_count)
+        .map(|index| {
+            let dist = Arc::clone(&dist);
+            let share = n / worker_count + usize::from(index < n % worker_count);
+            let child_seed = worker_seed(seed, index as u64);
+            std::thread::spawn(move || {
+                let mut rng = seeded_rng(child_seed);
+                dist.sample_n_with(share, &mut rng)
+            })
+        })
+        .collect();
+    handles.into_iter().flat_map(|h| h.join().expect("a parallel_sample worker thread panicked")).collect()
+}
+
+/// The signature every distribution's sampler implements: draws one value
+/// given an explicit source of randomness.
+pub type Sampler = dyn Fn(&mut dyn RngCore) -> Value + Send + Sync;
+
+/// A samplable probability distribution.
+///
+/// `params` holds the numeric parameters the distribution was built with
+/// (e.g. `[mean, std]` for `normal`), kept around so features like
+/// [`Distribution::reparam_sample`] and structural equality don't need to
+/// re-derive them from the opaque `sampler` closure.
+///
+/// `sampler` takes its randomness as an explicit `&mut dyn RngCore` rather
+/// than reaching for `rand::thread_rng()` itself, so callers can plug in a
+/// seeded RNG (via [`Distribution::sample_with`]) for reproducible runs.
+#[derive(Clone)]
+pub struct Distribution {
+    pub name: String,
+    pub params: Vec<f64>,
+    pub sampler: Arc<Sampler>,
+}
+
+impl Distribution {
+    /// A thin wrapper over [`Distribution::sample_with`] using the global
+    /// RNG (see [`set_global_seed`]) if one has been seeded on this thread,
+    /// or `rand::thread_rng()` otherwise.
+    pub fn sample(&self) -> Value {
+        with_global_or_thread_rng(|rng| self.sample_with(rng))
+    }
+
+    /// Draws a single value using the given RNG, for reproducible sequences
+    /// when `rng` was seeded (e.g. via [`seeded_rng`]).
+    pub fn sample_with(&self, rng: &mut dyn RngCore) -> Value {
+        (self.sampler)(rng)
+    }
+
+    /// Draws `n` values in sequence from the given RNG.
+    pub fn sample_n_with(&self, n: usize, rng: &mut dyn RngCore) -> Vec<Value> {
+        (0..n).map(|_| self.sample_with(rng)).collect()
+    }
+
+    /// The reparameterization trick for location-scale families: maps a
+    /// standard draw `eps` through the distribution's own parameters
+    /// (`mean + std * eps` for `normal`) so that sampling becomes a
+    /// differentiable function of `eps` and the parameters, not of an
+    /// opaque RNG call. Returns `None` for families this doesn't apply to.
+    pub fn reparam_sample(&self, eps: f64) -> Option<f64> {
+        match self.name.as_str() {
+            "normal" => {
+                let [mean, std] = self.params[..] else { return None };
+                Some(mean + std * eps)
+            }
+            "uniform" => {
+                let [low, high] = self.params[..] else { return None };
+                // Standard normal eps isn't naturally uniform; reinterpret
+                // eps as already lying in [0, 1) via the standard normal
+                // CDF isn't needed here since uniform's own parameters are
+                // already location-scale over the unit interval.
+                Some(low + (high - low) * eps)
+            }
+            _ => None,
+        }
+    }
+
+    /// The distribution's analytic mean, for the families where it has a
+    /// closed form. `None` for families like `histogram`/`kde` whose mean
+    /// can only be estimated from samples.
+    pub fn mean(&self) -> Option<f64> {
+        match (self.name.as_str(), &self.params[..]) {
+            ("uniform", [low, high]) => Some((low + high) / 2.0),
+            ("normal", [mean, _std]) => Some(*mean),
+            ("bernoulli", [p]) => Some(*p),
+            _ => None,
+        }
+    }
+
+    /// The distribution's analytic variance, for the families where it has
+    /// a closed form.
+    pub fn variance(&self) -> Option<f64> {
+        match (self.name.as_str(), &self.params[..]) {
+            ("uniform", [low, high]) => Some((high - low).powi(2) / 12.0),
+            ("normal", [_mean, std]) => Some(std * std),
+            ("bernoulli", [p]) => Some(p * (1.0 - p)),
+            _ => None,
+        }
+    }
+
+    /// The interval the distribution's values fall in, for the families
+    /// where it's known from the parameters alone (as opposed to only
+    /// observable from samples).
+    pub fn support(&self) -> Option<(f64, f64)> {
+        match (self.name.as_str(), &self.params[..]) {
+            ("uniform", [low, high]) => Some((*low, *high)),
+            ("normal", _) => Some((f64::NEG_INFINITY, f64::INFINITY)),
+            ("bernoulli", _) => Some((0.0, 1.0)),
+            _ => None,
+        }
+    }
+
+    /// Structural equality by family name and parameters, ignoring the
+    /// opaque `sampler` closure (which, unlike [`Value`], has no meaningful
+    /// notion of equality). Lets tests assert e.g. `normal(0,1) == normal(0,1)`
+    /// without comparing function pointers.
+    pub fn structurally_eq(&self, other: &Distribution) -> bool {
+        self.name == other.name && self.params == other.params
+    }
+
+    /// The log-density (log-mass, for discrete families) of `value` under
+    /// this distribution, for the families where it has a closed form.
+    /// Used by `Expr::Observe` for likelihood weighting. `None` when the
+    /// family has no closed form, or `value` isn't numeric/boolean.
+    pub fn log_density(&self, value: &Value) -> Option<f64> {
+        let x = match value {
+            Value::Float(x) => *x,
+            Value::Int(i) => *i as f64,
+            Value::Bool(b) => {
+                if *b {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            _ => return None,
+        };
+        match (self.name.as_str(), &self.params[..]) {
+            ("uniform", [low, high]) => {
+                if x < *low || x > *high {
+                    Some(f64::NEG_INFINITY)
+                } else {
+                    Some(-(high - low).ln())
+                }
+            }
+            ("normal", [mean, std]) => {
+                let z = (x - mean) / std;
+                Some(-0.5 * z * z - std.ln() - 0.5 * (2.0 * std::f64::consts::PI).ln())
+            }
+            ("bernoulli", [p]) => {
+                if x == 1.0 {
+                    Some(p.ln())
+                } else if x == 0.0 {
+                    Some((1.0 - p).ln())
+                } else {
+                    Some(f64::NEG_INFINITY)
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A continuous uniform distribution over `[low, high)`. Errors if `low` is
+/// not strictly less than `high`, which would otherwise panic at sample
+/// time (`rng.gen_range` requires a non-empty range).
+pub fn uniform(low: f64, high: f64) -> Result<Value, String> {
+    if !low.is_finite() || !high.is_finite() || low >= high {
+        return Err(format!("uniform's low ({low}) must be a finite number less than high ({high})"));
+    }
+    let params = vec![low, high];
+    Ok(Value::Dist(Arc::new(Distribution {
+        name: "uniform".into(),
+        params,
+        sampler: Arc::new(move |rng| Value::Float(rng.gen_range(low..high))),
+    })))
+}
+
+/// A normal (Gaussian) distribution with the given `mean` and standard
+/// deviation `std`. Errors if `std` isn't a positive, finite number, which
+/// would otherwise panic at construction time (`rand_distr::Normal::new`).
+pub fn normal(mean: f64, std: f64) -> Result<Value, String> {
+    if !(std > 0.0 && std.is_finite()) {
+        return Err(format!("normal's std ({std}) must be a positive, finite number"));
+    }
+    let params = vec![mean, std];
+    let dist = rand_distr::Normal::new(mean, std).map_err(|e| format!("invalid normal parameters: {e}"))?;
+    Ok(Value::Dist(Arc::new(Distribution {
+        name: "normal".into(),
+        params,
+        sampler: Arc::new(move |rng| Value::Float(dist.sample(rng))),
+    })))
+}
+
+/// An unweighted `bet { a, b, c }`: a categorical distribution that draws
+/// uniformly from `values`. Unlike the other constructors here, `values`
+/// need not be numeric, so [`Distribution::mean`]/[`variance`][Distribution::variance]/
+/// [`support`][Distribution::support] have no closed form for it.
+pub fn categorical(values: Vec<Value>) -> Value {
+    let params = vec![values.len() as f64];
+    Value::Dist(Arc::new(Distribution {
+        name: "categorical".into(),
+        params,
+        sampler: Arc::new(move |rng| {
+            let idx = rng.gen_range(0..values.len());
+            values[idx].clone()
+        }),
+    }))
+}
+
+/// `return e` inside a `do` block: a degenerate distribution ("point mass")
+/// that always samples to the same fixed `value`, used to lift a plain
+/// value into the distribution monad.
+pub fn point_mass(value: Value) -> Value {
+    Value::Dist(Arc::new(Distribution {
+        name: "point_mass".into(),
+        params: Vec::new(),
+        sampler: Arc::new(move |_rng| value.clone()),
+    }))
+}
+
+/// `fmap` over the distribution monad: builds a new distribution that draws
+/// from `dist` and applies `f` to the result, so e.g. mapping `uniform(0,1)`
+/// through `|x| x*10` yields samples in `[0, 10)`. The new distribution has
+/// no closed-form moments of its own, since `f` is an opaque function.
+pub fn map_dist(dist: Arc<Distribution>, f: Arc<dyn Fn(Value) -> Value + Send + Sync>) -> Value {
+    Value::Dist(Arc::new(Distribution {
+        name: "map".into(),
+        params: Vec::new(),
+        sampler: Arc::new(move |rng| f(dist.sample_with(rng))),
+    }))
+}
+
+/// A mixture of any number of distributions: each draw picks a component at
+/// random, weighted by `components`' second element, then samples from it.
+/// Errors if `components` is empty, any weight isn't positive, or any
+/// component isn't a [`Value::Dist`].
+pub fn mixture_n(components: Vec<(Value, f64)>) -> Result<Value, String> {
+    if components.is_empty() {
+        return Err("mixture_n needs at least one component".to_string());
+    }
+    let mut dists = Vec::with_capacity(components.len());
+    let mut weights = Vec::with_capacity(components.len());
+    for (value, weight) in components {
+        let Value::Dist(dist) = value else {
+            return Err(format!("mixture_n expects a distribution for each component, found `{value}`"));
+        };
+        if !(weight > 0.0) {
+            return Err(format!("mixture_n's weights must all be positive, found {weight}"));
+        }
+        dists.push(dist);
+        weights.push(weight);
+    }
+    let index = WeightedIndex::new(&weights).map_err(|e| format!("invalid mixture weights: {e}"))?;
+    Ok(Value::Dist(Arc::new(Distribution {
+        name: "mixture".into(),
+        params: weights,
+        sampler: Arc::new(move |rng| {
+            let chosen = index.sample(rng);
+            dists[chosen].sample_with(rng)
+        }),
+    })))
+}
+
+/// A Dirichlet prior over the `alpha.len()`-simplex: each sample is a
+/// [`Value::List`] of floats summing to 1. Errors if `alpha` is empty or any
+/// entry isn't positive.
+pub fn dirichlet(alpha: Vec<f64>) -> Result<Value, String> {
+    if alpha.is_empty() {
+        return Err("dirichlet needs at least one alpha parameter".to_string());
+    }
+    if alpha.iter().any(|a| *a <= 0.0) {
+        return Err("dirichlet's alpha parameters must all be positive".to_string());
+    }
+    let dist = rand_distr::Dirichlet::new(&alpha).map_err(|e| format!("invalid dirichlet parameters: {e}"))?;
+    let params = alpha;
+    Ok(Value::Dist(Arc::new(Distribution {
+        name: "dirichlet".into(),
+        params,
+        sampler: Arc::new(move |rng| {
+            let draw: Vec<f64> = dist.sample(rng);
+            Value::List(draw.into_iter().map(Value::Float).collect())
+        }),
+    })))
+}
+
+/// A multinomial likelihood: `n` independent draws from a categorical
+/// distribution over `probs`, returning a [`Value::List`] of per-category
+/// counts. Errors if `probs` is empty, any entry is negative, or the
+/// entries don't sum to ~1.
+pub fn multinomial(n: u64, probs: Vec<f64>) -> Result<Value, String> {
+    if probs.is_empty() {
+        return Err("multinomial needs at least one probability".to_string());
+    }
+    if probs.iter().any(|p| *p < 0.0) {
+        return Err("multinomial's probabilities must all be non-negative".to_string());
+    }
+    let total: f64 = probs.iter().sum();
+    if (total - 1.0).abs() > 1e-6 {
+        return Err(format!("multinomial's probabilities must sum to 1, found {total}"));
+    }
+    let index = WeightedIndex::new(&probs).map_err(|e| format!("invalid multinomial probabilities: {e}"))?;
+    let params = probs.clone();
+    let k = probs.len();
+    Ok(Value::Dist(Arc::new(Distribution {
+        name: "multinomial".into(),
+        params,
+        sampler: Arc::new(move |rng| {
+            let mut counts = vec![0i64; k];
+            for _ in 0..n {
+                counts[index.sample(rng)] += 1;
+            }
+            Value::List(counts.into_iter().map(Value::Int).collect())
+        }),
+    })))
+}
+
+/// A Bernoulli distribution: `true` with probability `p`. Errors if `p` is
+/// outside `[0.0, 1.0]`, which would otherwise panic at sample time
+/// (`rng.gen_bool` requires a probability in that range).
+pub fn bernoulli(p: f64) -> Result<Value, String> {
+    if !(0.0..=1.0).contains(&p) {
+        return Err(format!("bernoulli's p ({p}) must be in [0.0, 1.0]"));
+    }
+    let params = vec![p];
+    Ok(Value::Dist(Arc::new(Distribution {
+        name: "bernoulli".into(),
+        params,
+        sampler: Arc::new(move |rng| Value::Bool(rng.gen_bool(p))),
+    })))
+}
+
+/// Builds a categorical distribution over `bin_centers`, weighted by
+/// `counts`, so an empirical histogram becomes a samplable [`Value::Dist`].
+/// Errors if the inputs have mismatched lengths or every count is zero.
+pub fn distribution_from_histogram(bin_centers: &[f64], counts: &[u32]) -> Result<Value, String> {
+    if bin_centers.len() != counts.len() {
+        return Err(format!(
+            "bin_centers has {} entries but counts has {}",
+            bin_centers.len(),
+            counts.len()
+        ));
+    }
+    let total: u32 = counts.iter().sum();
+    if total == 0 {
+        return Err("histogram has no observations to sample from".to_string());
+    }
+
+    let bin_centers = bin_centers.to_vec();
+    let weights = counts.to_vec();
+    let params = bin_centers.clone();
+    Ok(Value::Dist(Arc::new(Distribution {
+        name: "histogram".into(),
+        params,
+        sampler: Arc::new(move |rng| {
+            let target = rng.gen_range(0..total);
+            let mut cumulative = 0u32;
+            for (center, count) in bin_centers.iter().zip(&weights) {
+                cumulative += count;
+                if target < cumulative {
+                    return Value::Float(*center);
+                }
+            }
+            Value::Float(*bin_centers.last().unwrap())
+        }),
+    })))
+}
+
+/// Scott's rule: a simple, widely-used bandwidth default for Gaussian KDE.
+fn scotts_rule_bandwidth(samples: &[f64]) -> f64 {
+    let n = samples.len() as f64;
+    let mean = samples.iter().sum::<f64>() / n;
+    let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+    variance.sqrt() * n.powf(-1.0 / 5.0)
+}
+
+/// A kernel density estimate as a samplable distribution: each sample
+/// draws a data point uniformly at random and adds Gaussian noise with
+/// standard deviation `bandwidth` (Scott's rule if not given). Smoother
+/// than [`distribution_from_histogram`] since it isn't bucketed.
+pub fn kde_distribution(samples: &[f64], bandwidth: Option<f64>) -> Value {
+    let bandwidth = bandwidth.unwrap_or_else(|| scotts_rule_bandwidth(samples));
+    let data = samples.to_vec();
+    let params = vec![bandwidth];
+    Value::Dist(Arc::new(Distribution {
+        name: "kde".into(),
+        params,
+        sampler: Arc::new(move |rng| {
+            let point = data[rng.gen_range(0..data.len())];
+            let noise: f64 = StandardNormal.sample(rng);
+            Value::Float(point + bandwidth * noise)
+        }),
+    }))
+}
+
+/// The value at the `p`-th percentile (0-100) of `data`, using linear
+/// interpolation between order statistics (NumPy's default "type 7"
+/// method), so e.g. the 50th percentile of an even-length list is the
+/// average of its two middle elements rather than snapping to one of them.
+/// Panics on an empty `data`, like indexing an empty slice would.
+pub fn percentile(data: &[f64], p: f64) -> f64 {
+    let mut sorted = data.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let h = p / 100.0 * (sorted.len() as f64 - 1.0);
+    let lo = h.floor() as usize;
+    let hi = h.ceil() as usize;
+    if lo == hi {
+        sorted[lo]
+    } else {
+        sorted[lo] + (h - lo as f64) * (sorted[hi] - sorted[lo])
+    }
+}
+
+fn as_float(v: &Value, pos: &str) -> Result<f64, String> {
+    match v {
+        Value::Float(x) => Ok(*x),
+        Value::Int(i) => Ok(*i as f64),
+        other => Err(format!("expected a number for {pos}, found {other}")),
+    }
+}
+
+fn native(name: &str, arity: usize, func: impl Fn(&[Value]) -> Result<Value, String> + Send + Sync + 'static) -> (String, NativeFunction) {
+    (
+        name.to_string(),
+        NativeFunction {
+            name: name.to_string(),
+            arity,
+            func: Arc::new(func),
+        },
+    )
+}
+
+/// The native functions this module contributes to the interpreter's
+/// prelude.
+pub fn native_functions() -> HashMap<String, NativeFunction> {
+    let mut m = HashMap::new();
+    let (name, f) = native("uniform", 2, |args| {
+        let low = as_float(&args[0], "low")?;
+        let high = as_float(&args[1], "high")?;
+        uniform(low, high)
+    });
+    m.insert(name, f);
+    let (name, f) = native("normal", 2, |args| {
+        let mean = as_float(&args[0], "mean")?;
+        let std = as_float(&args[1], "std")?;
+        normal(mean, std)
+    });
+    m.insert(name, f);
+    let (name, f) = native("bernoulli", 1, |args| {
+        let p = as_float(&args[0], "p")?;
+        bernoulli(p)
+    });
+    m.insert(name, f);
+    let (name, f) = native("distribution_from_histogram", 2, |args| {
+        let bin_centers = match &args[0] {
+            Value::List(items) => items.iter().map(|v| as_float(v, "bin center")).collect::<Result<Vec<_>, _>>()?,
+            other => return Err(format!("expected a list of bin centers, found {other}")),
+        };
+        let counts = match &args[1] {
+            Value::List(items) => items
+                .iter()
+                .map(|v| match v {
+                    Value::Int(i) if *i >= 0 => Ok(*i as u32),
+                    other => Err(format!("expected a non-negative count, found {other}")),
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            other => return Err(format!("expected a list of counts, found {other}")),
+        };
+        distribution_from_histogram(&bin_centers, &counts)
+    });
+    m.insert(name, f);
+    let (name, f) = native("mixture_n", 1, |args| {
+        let components = match &args[0] {
+            Value::List(items) => items
+                .iter()
+                .map(|item| match item {
+                    Value::Tuple(pair) if pair.len() == 2 => {
+                        Ok((pair[0].clone(), as_float(&pair[1], "a mixture weight")?))
+                    }
+                    other => Err(format!("expected a (distribution, weight) tuple, found {other}")),
+                })
+                .collect::<Result<Vec<_>, _>>()?,
+            other => return Err(format!("expected a list of (distribution, weight) tuples, found {other}")),
+        };
+        mixture_n(components)
+    });
+    m.insert(name, f);
+    let (name, f) = native("percentile", 2, |args| {
+        let data = match &args[0] {
+            Value::List(items) => items.iter().map(|v| as_float(v, "a list element")).collect::<Result<Vec<_>, _>>()?,
+            other => return Err(format!("expected a list of numbers, found {other}")),
+        };
+        let p = as_float(&args[1], "p")?;
+        if data.is_empty() {
+            return Ok(Value::Unit);
+        }
+        Ok(Value::Float(percentile(&data, p)))
+    });
+    m.insert(name, f);
+    let (name, f) = native("dirichlet", 1, |args| {
+        let alpha = match &args[0] {
+            Value::List(items) => items.iter().map(|v| as_float(v, "an alpha parameter")).collect::<Result<Vec<_>, _>>()?,
+            other => return Err(format!("expected a list of alpha parameters, found {other}")),
+        };
+        dirichlet(alpha)
+    });
+    m.insert(name, f);
+    let (name, f) = native("multinomial", 2, |args| {
+        let n = match &args[0] {
+            Value::Int(i) if *i >= 0 => *i as u64,
+            other => return Err(format!("expected a non-negative integer for n, found {other}")),
+        };
+        let probs = match &args[1] {
+            Value::List(items) => items.iter().map(|v| as_float(v, "a probability")).collect::<Result<Vec<_>, _>>()?,
+            other => return Err(format!("expected a list of probabilities, found {other}")),
+        };
+        multinomial(n, probs)
+    });
+    m.insert(name, f);
+    let (name, f) = native("kde_distribution", 2, |args| {
+        let samples = match &args[0] {
+            Value::List(items) => items.iter().map(|v| as_float(v, "sample")).collect::<Result<Vec<_>, _>>()?,
+            other => return Err(format!("expected a list of samples, found {other}")),
+        };
+        let bandwidth = match &args[1] {
+            Value::Unit => None,
+            other => Some(as_float(other, "bandwidth")?),
+        };
+        Ok(kde_distribution(&samples, bandwidth))
+    });
+    m.insert(name, f);
+    m
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normal_reparam_sample_is_affine() {
+        let dist = normal(2.0, 3.0).unwrap();
+        let Value::Dist(d) = dist else { panic!("expected Dist") };
+        assert_eq!(d.reparam_sample(1.0), Some(5.0));
+    }
+
+    #[test]
+    fn uniform_mean_and_variance_match_the_closed_form() {
+        let dist = uniform(0.0, 1.0).unwrap();
+        let Value::Dist(d) = dist else { panic!("expected Dist") };
+        assert_eq!(d.mean(), Some(0.5));
+        assert_eq!(d.variance(), Some(1.0 / 12.0));
+        assert_eq!(d.support(), Some((0.0, 1.0)));
+    }
+
+    #[test]
+    fn normal_support_is_unbounded() {
+        let dist = normal(0.0, 1.0).unwrap();
+        let Value::Dist(d) = dist else { panic!("expected Dist") };
+        assert_eq!(d.support(), Some((f64::NEG_INFINITY, f64::INFINITY)));
+    }
+
+    #[test]
+    fn histogram_has_no_closed_form_moments() {
+        let dist = distribution_from_histogram(&[0.0, 1.0], &[1, 1]).unwrap();
+        let Value::Dist(d) = dist else { panic!("expected Dist") };
+        assert_eq!(d.mean(), None);
+        assert_eq!(d.variance(), None);
+        assert_eq!(d.support(), None);
+    }
+
+    #[test]
+    fn bernoulli_reparam_is_not_applicable() {
+        let dist = bernoulli(0.5).unwrap();
+        let Value::Dist(d) = dist else { panic!("expected Dist") };
+        assert_eq!(d.reparam_sample(1.0), None);
+    }
+
+    #[test]
+    fn histogram_distribution_heavily_favors_the_highest_count_bin() {
+        let bin_centers = [0.0, 1.0, 2.0];
+        let counts = [1u32, 1, 1000];
+        let dist = distribution_from_histogram(&bin_centers, &counts).unwrap();
+        let Value::Dist(d) = dist else { panic!("expected Dist") };
+
+        let mut favored = 0;
+        for _ in 0..1000 {
+            if let Value::Float(x) = d.sample() {
+                if x == 2.0 {
+                    favored += 1;
+                }
+            }
+        }
+        assert!(favored > 950, "only {favored}/1000 samples landed on the dominant bin");
+    }
+
+    #[test]
+    fn bernoulli_log_density_matches_the_mass_function() {
+        let dist = bernoulli(0.3).unwrap();
+        let Value::Dist(d) = dist else { panic!("expected Dist") };
+        assert_eq!(d.log_density(&Value::Bool(true)), Some(0.3_f64.ln()));
+        assert_eq!(d.log_density(&Value::Bool(false)), Some(0.7_f64.ln()));
+    }
+
+    #[test]
+    fn uniform_log_density_is_negative_infinity_outside_support() {
+        let dist = uniform(0.0, 1.0).unwrap();
+        let Value::Dist(d) = dist else { panic!("expected Dist") };
+        assert_eq!(d.log_density(&Value::Float(0.5)), Some(0.0));
+        assert_eq!(d.log_density(&Value::Float(2.0)), Some(f64::NEG_INFINITY));
+    }
+
+    #[test]
+    fn histogram_has_no_closed_form_log_density() {
+        let dist = distribution_from_histogram(&[0.0, 1.0], &[1, 1]).unwrap();
+        let Value::Dist(d) = dist else { panic!("expected Dist") };
+        assert_eq!(d.log_density(&Value::Float(0.5)), None);
+    }
+
+    #[test]
+    fn categorical_always_draws_one_of_the_given_values() {
+        let dist = categorical(vec![Value::Int(1), Value::Int(2), Value::Int(3)]);
+        let Value::Dist(d) = dist else { panic!("expected Dist") };
+        for _ in 0..50 {
+            assert!(matches!(d.sample(), Value::Int(1) | Value::Int(2) | Value::Int(3)));
+        }
+    }
+
+    #[test]
+    fn point_mass_always_samples_to_the_same_value() {
+        let dist = point_mass(Value::Int(7));
+        let Value::Dist(d) = dist else { panic!("expected Dist") };
+        assert_eq!(d.sample(), Value::Int(7));
+        assert_eq!(d.sample(), Value::Int(7));
+    }
+
+    #[test]
+    fn identically_seeded_rngs_produce_identical_sample_sequences() {
+        let dist = normal(0.0, 1.0).unwrap();
+        let Value::Dist(d) = dist else { panic!("expected Dist") };
+
+        let mut rng_a = seeded_rng(42);
+        let mut rng_b = seeded_rng(42);
+        let a = d.sample_n_with(10, &mut rng_a);
+        let b = d.sample_n_with(10, &mut rng_b);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn differently_seeded_rngs_diverge() {
+        let dist = normal(0.0, 1.0).unwrap();
+        let Value::Dist(d) = dist else { panic!("expected Dist") };
+
+        let mut rng_a = seeded_rng(1);
+        let mut rng_b = seeded_rng(2);
+        let a = d.sample_n_with(10, &mut rng_a);
+        let b = d.sample_n_with(10, &mut rng_b);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn map_dist_transforms_every_sample_through_f() {
+        let Value::Dist(d) = uniform(0.0, 1.0).unwrap() else { panic!("expected Dist") };
+        let mapped = map_dist(d, Arc::new(|v| match v {
+            Value::Float(x) => Value::Float(x * 2.0),
+            other => other,
+        }));
+        let Value::Dist(mapped) = mapped else { panic!("expected Dist") };
+        for _ in 0..100 {
+            let Value::Float(x) = mapped.sample() else { panic!("expected Float") };
+            assert!((0.0..2.0).contains(&x), "{x} is outside [0, 2)");
+        }
+    }
+
+    #[test]
+    fn mixture_n_rejects_an_empty_component_list() {
+        assert!(mixture_n(Vec::new()).is_err());
+    }
+
+    #[test]
+    fn mixture_n_rejects_a_non_distribution_component() {
+        assert!(mixture_n(vec![(Value::Int(1), 1.0)]).is_err());
+    }
+
+    #[test]
+    fn mixture_n_of_three_normals_has_a_mean_near_the_weighted_average() {
+        let components = vec![(normal(0.0, 0.1).unwrap(), 1.0), (normal(10.0, 0.1).unwrap(), 2.0), (normal(20.0, 0.1).unwrap(), 3.0)];
+        let expected_mean = (0.0 * 1.0 + 10.0 * 2.0 + 20.0 * 3.0) / 6.0;
+
+        let mixed = mixture_n(components).unwrap();
+        let Value::Dist(d) = mixed else { panic!("expected Dist") };
+
+        let mut rng = seeded_rng(7);
+        let n = 20_000;
+        let sum: f64 = (0..n)
+            .map(|_| match d.sample_with(&mut rng) {
+                Value::Float(x) => x,
+                other => panic!("expected Float, found {other}"),
+            })
+            .sum();
+        let observed_mean = sum / n as f64;
+        assert!(
+            (observed_mean - expected_mean).abs() < 0.5,
+            "observed mean {observed_mean} too far from expected {expected_mean}"
+        );
+    }
+
+    #[test]
+    fn uniform_rejects_a_low_that_is_not_strictly_less_than_high() {
+        assert!(uniform(1.0, 1.0).is_err());
+        assert!(uniform(2.0, 1.0).is_err());
+    }
+
+    #[test]
+    fn normal_rejects_a_non_positive_or_non_finite_std() {
+        assert!(normal(0.0, 0.0).is_err());
+        assert!(normal(0.0, -1.0).is_err());
+        assert!(normal(0.0, f64::NAN).is_err());
+        assert!(normal(0.0, f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn bernoulli_rejects_a_p_outside_zero_one() {
+        assert!(bernoulli(-0.1).is_err());
+        assert!(bernoulli(1.1).is_err());
+    }
+
+    #[test]
+    fn dirichlet_rejects_an_empty_alpha() {
+        assert!(dirichlet(Vec::new()).is_err());
+    }
+
+    #[test]
+    fn dirichlet_rejects_a_non_positive_alpha_entry() {
+        assert!(dirichlet(vec![1.0, 0.0, 1.0]).is_err());
+    }
+
+    #[test]
+    fn dirichlet_samples_are_a_simplex_point() {
+        let dist = dirichlet(vec![1.0, 2.0, 3.0]).unwrap();
+        let Value::Dist(d) = dist else { panic!("expected Dist") };
+        let Value::List(draw) = d.sample() else { panic!("expected List") };
+        assert_eq!(draw.len(), 3);
+        let sum: f64 = draw
+            .iter()
+            .map(|v| match v {
+                Value::Float(x) => *x,
+                other => panic!("expected Float, found {other}"),
+            })
+            .sum();
+        assert!((sum - 1.0).abs() < 1e-9, "sum was {sum}");
+    }
+
+    #[test]
+    fn multinomial_rejects_probabilities_that_do_not_sum_to_one() {
+        assert!(multinomial(10, vec![0.5, 0.2]).is_err());
+    }
+
+    #[test]
+    fn multinomial_counts_sum_to_n_and_have_one_entry_per_category() {
+        let dist = multinomial(100, vec![0.2, 0.3, 0.5]).unwrap();
+        let Value::Dist(d) = dist else { panic!("expected Dist") };
+        let Value::List(draw) = d.sample() else { panic!("expected List") };
+        assert_eq!(draw.len(), 3);
+        let sum: i64 = draw
+            .iter()
+            .map(|v| match v {
+                Value::Int(i) => *i,
+                other => panic!("expected Int, found {other}"),
+            })
+            .sum();
+        assert_eq!(sum, 100);
+    }
+
+    #[test]
+    fn identically_parameterized_normals_are_structurally_equal() {
+        let Value::Dist(a) = normal(0.0, 1.0).unwrap() else { panic!("expected Dist") };
+        let Value::Dist(b) = normal(0.0, 1.0).unwrap() else { panic!("expected Dist") };
+        assert!(a.structurally_eq(&b));
+    }
+
+    #[test]
+    fn differently_parameterized_normals_are_not_structurally_equal() {
+        let Value::Dist(a) = normal(0.0, 1.0).unwrap() else { panic!("expected Dist") };
+        let Value::Dist(b) = normal(1.0, 1.0).unwrap() else { panic!("expected Dist") };
+        assert!(!a.structurally_eq(&b));
+    }
+
+    #[test]
+    fn distributions_from_different_families_are_not_structurally_equal() {
+        let Value::Dist(a) = uniform(0.0, 1.0).unwrap() else { panic!("expected Dist") };
+        let Value::Dist(b) = bernoulli(0.5).unwrap() else { panic!("expected Dist") };
+        assert!(!a.structurally_eq(&b));
+    }
+
+    #[test]
+    fn percentile_interpolates_between_order_statistics() {
+        let data = [1.0, 2.0, 3.0, 4.0];
+        assert_eq!(percentile(&data, 0.0), 1.0);
+        assert_eq!(percentile(&data, 50.0), 2.5);
+        assert_eq!(percentile(&data, 100.0), 4.0);
+    }
+
+    #[test]
+    fn percentile_matches_a_known_numpy_value() {
+        // np.percentile([1, 2, 3, 4, 5, 6, 7, 8, 9, 10], 30) == 3.7
+        let data: Vec<f64> = (1..=10).map(f64::from).collect();
+        assert!((percentile(&data, 30.0) - 3.7).abs() < 1e-9);
+    }
+
+    #[test]
+    fn percentile_native_reports_unit_for_an_empty_list() {
+        let call = native_functions();
+        let result = (call["percentile"].func)(&[Value::List(im::Vector::new()), Value::Float(50.0)]).unwrap();
+        assert_eq!(result, Value::Unit);
+    }
+
+    #[test]
+    fn the_global_seed_makes_plain_sample_calls_reproducible() {
+        let dist = normal(0.0, 1.0).unwrap();
+        let Value::Dist(d) = dist else { panic!("expected Dist") };
+
+        let a = with_global_rng(99, || (0..10).map(|_| d.sample()).collect::<Vec<_>>());
+        let b = with_global_rng(99, || (0..10).map(|_| d.sample()).collect::<Vec<_>>());
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn two_parallel_runs_with_the_same_seed_and_worker_count_match() {
+        let Value::Dist(dist) = normal(0.0, 1.0).unwrap() else { panic!("expected Dist") };
+        let a = parallel_sample(Arc::clone(&dist), 1000, 42, 4);
+        let b = parallel_sample(dist, 1000, 42, 4);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn parallel_sample_draws_exactly_n_samples() {
+        let Value::Dist(dist) = normal(0.0, 1.0).unwrap() else { panic!("expected Dist") };
+        let draws = parallel_sample(dist, 101, 1, 8);
+        assert_eq!(draws.len(), 101);
+    }
+
+    #[test]
+    fn worker_seed_is_deterministic_and_distinguishes_workers() {
+        assert_eq!(worker_seed(42, 0), worker_seed(42, 0));
+        assert_ne!(worker_seed(42, 0), worker_seed(42, 1));
+    }
+
+    #[test]
+    fn kde_samples_concentrate_around_dense_regions() {
+        let mut samples: Vec<f64> = vec![0.0; 95];
+        samples.extend(vec![10.0; 5]);
+        let dist = kde_distribution(&samples, Some(0.1));
+        let Value::Dist(d) = dist else { panic!("expected Dist") };
+
+        let mut near_zero = 0;
+        for _ in 0..1000 {
+            if let Value::Float(x) = d.sample() {
+                if x.abs() < 1.0 {
+                    near_zero += 1;
+                }
+            }
+        }
+        assert!(near_zero > 900, "only {near_zero}/1000 samples landed near the dense region");
+    }
+}
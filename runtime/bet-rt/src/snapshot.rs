@@ -0,0 +1,91 @@
+//! File-backed snapshot testing for [`Value`], with a float tolerance so
+//! sampled or estimated outputs don't churn the snapshot on every run.
+
+use std::fs;
+use std::path::Path;
+
+use crate::json::{json_to_value, value_to_json};
+use crate::value::Value;
+
+/// Compares `actual` against the snapshot stored at `path`, allowing floats
+/// to differ by up to `tol`. If `path` doesn't exist yet, writes `actual`
+/// there as the new snapshot and succeeds, per the usual
+/// record-on-first-run snapshot-testing contract.
+pub fn snapshot_assert(actual: &Value, path: &str, tol: f64) -> Result<(), String> {
+    if !Path::new(path).exists() {
+        let json = serde_json::to_string_pretty(&value_to_json(actual))
+            .map_err(|e| format!("failed to serialize snapshot: {e}"))?;
+        fs::write(path, json).map_err(|e| format!("failed to write snapshot `{path}`: {e}"))?;
+        return Ok(());
+    }
+
+    let contents = fs::read_to_string(path).map_err(|e| format!("failed to read snapshot `{path}`: {e}"))?;
+    let json: serde_json::Value =
+        serde_json::from_str(&contents).map_err(|e| format!("failed to parse snapshot `{path}`: {e}"))?;
+    let expected = json_to_value(&json);
+
+    if values_approx_eq(actual, &expected, tol) {
+        Ok(())
+    } else {
+        Err(format!("snapshot mismatch at `{path}`: `{actual}` != `{expected}` (tol {tol})"))
+    }
+}
+
+/// Structural equality that treats numbers within `tol` of each other as
+/// equal, recursing into lists/tuples/maps. Sets and every other variant
+/// are compared with [`Value`]'s own exact `PartialEq`.
+fn values_approx_eq(a: &Value, b: &Value, tol: f64) -> bool {
+    match (a, b) {
+        (Value::Float(x), Value::Float(y)) => (x - y).abs() <= tol,
+        (Value::Float(x), Value::Int(y)) | (Value::Int(y), Value::Float(x)) => (x - *y as f64).abs() <= tol,
+        (Value::List(xs), Value::List(ys)) => {
+            xs.len() == ys.len() && xs.iter().zip(ys.iter()).all(|(x, y)| values_approx_eq(x, y, tol))
+        }
+        (Value::Tuple(xs), Value::Tuple(ys)) => {
+            xs.len() == ys.len() && xs.iter().zip(ys.iter()).all(|(x, y)| values_approx_eq(x, y, tol))
+        }
+        (Value::Map(xs), Value::Map(ys)) => {
+            xs.len() == ys.len() && xs.iter().all(|(k, x)| ys.get(k).is_some_and(|y| values_approx_eq(x, y, tol)))
+        }
+        _ => a == b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use im::HashMap as ImMap;
+
+    fn snapshot_path(dir: &tempfile::TempDir, name: &str) -> String {
+        dir.path().join(name).to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn a_missing_snapshot_is_recorded_and_then_passes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = snapshot_path(&dir, "new.json");
+        let value = Value::Map(ImMap::unit("mean".to_string(), Value::Float(0.5)));
+
+        assert_eq!(snapshot_assert(&value, &path, 0.01), Ok(()));
+        assert_eq!(snapshot_assert(&value, &path, 0.01), Ok(()));
+    }
+
+    #[test]
+    fn a_float_within_tolerance_passes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = snapshot_path(&dir, "close.json");
+        snapshot_assert(&Value::Float(0.500), &path, 0.01).unwrap();
+
+        assert_eq!(snapshot_assert(&Value::Float(0.505), &path, 0.01), Ok(()));
+    }
+
+    #[test]
+    fn a_large_deviation_fails_and_reports_both_values() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = snapshot_path(&dir, "far.json");
+        snapshot_assert(&Value::Float(0.5), &path, 0.01).unwrap();
+
+        let err = snapshot_assert(&Value::Float(10.0), &path, 0.01).unwrap_err();
+        assert!(err.contains("0.5") && err.contains("10"), "error message was: {err}");
+    }
+}
@@ -0,0 +1,315 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use im::HashMap as ImMap;
+
+use crate::random::percentile;
+use crate::value::{NativeFunction, Value};
+
+fn as_floats(values: &[Value]) -> Result<Vec<f64>, String> {
+    values
+        .iter()
+        .map(|v| match v {
+            Value::Float(x) => Ok(*x),
+            Value::Int(i) => Ok(*i as f64),
+            other => Err(format!("expected a numeric sample, found {other}")),
+        })
+        .collect()
+}
+
+fn list_of_values(v: &Value) -> Result<Vec<Value>, String> {
+    match v {
+        Value::List(items) => Ok(items.iter().cloned().collect()),
+        other => Err(format!("expected a list, found {other}")),
+    }
+}
+
+/// The equal-tailed credible interval at `level` (e.g. `0.95` for a
+/// 95% interval): the `(1-level)/2` and `1-(1-level)/2` percentiles.
+pub fn credible_interval(samples: &[f64], level: f64) -> Option<(f64, f64)> {
+    if samples.is_empty() {
+        return None;
+    }
+    let alpha = (1.0 - level) / 2.0;
+    let lower = percentile(samples, alpha * 100.0);
+    let upper = percentile(samples, (1.0 - alpha) * 100.0);
+    Some((lower, upper))
+}
+
+/// The highest-density interval at `level`: the narrowest interval
+/// containing `level` of the sorted samples, found by sliding a
+/// fixed-width window across the sorted data.
+pub fn hdi(samples: &[f64], level: f64) -> Option<(f64, f64)> {
+    if samples.is_empty() {
+        return None;
+    }
+    let mut sorted = samples.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let n = sorted.len();
+    let span = ((level * n as f64).ceil() as usize).clamp(1, n);
+
+    let mut best = (sorted[0], sorted[span - 1]);
+    let mut best_width = best.1 - best.0;
+    for i in 1..=(n - span) {
+        let lo = sorted[i];
+        let hi = sorted[i + span - 1];
+        let width = hi - lo;
+        if width < best_width {
+            best_width = width;
+            best = (lo, hi);
+        }
+    }
+    Some(best)
+}
+
+/// The five-number summary behind a box plot: min, quartiles, and max.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoxStats {
+    pub min: f64,
+    pub q1: f64,
+    pub median: f64,
+    pub q3: f64,
+    pub max: f64,
+}
+
+impl BoxStats {
+    pub fn of(samples: &[f64]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        Some(BoxStats {
+            min: percentile(samples, 0.0),
+            q1: percentile(samples, 25.0),
+            median: percentile(samples, 50.0),
+            q3: percentile(samples, 75.0),
+            max: percentile(samples, 100.0),
+        })
+    }
+}
+
+/// A running mean/variance over a stream of values, updated one at a time
+/// via Welford's online algorithm so a caller never has to hold the whole
+/// stream in memory at once (e.g. while sampling in batches).
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct OnlineStats {
+    count: u64,
+    mean: f64,
+    m2: f64,
+}
+
+impl OnlineStats {
+    pub fn new() -> Self {
+        OnlineStats::default()
+    }
+
+    /// Folds `x` into the running statistics.
+    pub fn update(&mut self, x: f64) {
+        self.count += 1;
+        let delta = x - self.mean;
+        self.mean += delta / self.count as f64;
+        let delta2 = x - self.mean;
+        self.m2 += delta * delta2;
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.mean)
+    }
+
+    /// The population variance of the values seen so far, `None` until at
+    /// least one value has been folded in.
+    pub fn variance(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.m2 / self.count as f64)
+    }
+
+    pub fn std(&self) -> Option<f64> {
+        self.variance().map(f64::sqrt)
+    }
+}
+
+fn mean(samples: &[f64]) -> f64 {
+    samples.iter().sum::<f64>() / samples.len() as f64
+}
+
+fn std_dev(samples: &[f64], mean: f64) -> f64 {
+    let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / samples.len() as f64;
+    variance.sqrt()
+}
+
+/// A stats table covering mean, standard deviation, count, and the
+/// [`BoxStats`] five-number summary. Non-numeric entries in `values` are
+/// ignored rather than rejected, since a summary is meant to degrade
+/// gracefully over mixed data.
+pub fn summary(values: &[Value]) -> Option<ImMap<String, Value>> {
+    let samples: Vec<f64> = values
+        .iter()
+        .filter_map(|v| match v {
+            Value::Float(x) => Some(*x),
+            Value::Int(i) => Some(*i as f64),
+            _ => None,
+        })
+        .collect();
+    let box_stats = BoxStats::of(&samples)?;
+    let avg = mean(&samples);
+
+    let mut m = ImMap::new();
+    m.insert("mean".to_string(), Value::Float(avg));
+    m.insert("std".to_string(), Value::Float(std_dev(&samples, avg)));
+    m.insert("min".to_string(), Value::Float(box_stats.min));
+    m.insert("q1".to_string(), Value::Float(box_stats.q1));
+    m.insert("median".to_string(), Value::Float(box_stats.median));
+    m.insert("q3".to_string(), Value::Float(box_stats.q3));
+    m.insert("max".to_string(), Value::Float(box_stats.max));
+    m.insert("count".to_string(), Value::Int(samples.len() as i64));
+    Some(m)
+}
+
+fn native(name: &str, arity: usize, func: impl Fn(&[Value]) -> Result<Value, String> + Send + Sync + 'static) -> (String, NativeFunction) {
+    (
+        name.to_string(),
+        NativeFunction {
+            name: name.to_string(),
+            arity,
+            func: Arc::new(func),
+        },
+    )
+}
+
+fn interval_result(interval: Option<(f64, f64)>) -> Value {
+    match interval {
+        Some((lo, hi)) => Value::Tuple(vec![Value::Float(lo), Value::Float(hi)]),
+        None => Value::Unit,
+    }
+}
+
+pub fn native_functions() -> HashMap<String, NativeFunction> {
+    let mut m = HashMap::new();
+    let (name, f) = native("credible_interval", 2, |args| {
+        let samples = as_floats(&list_of_values(&args[0])?)?;
+        let level = match &args[1] {
+            Value::Float(x) => *x,
+            Value::Int(i) => *i as f64,
+            other => return Err(format!("expected a numeric level, found {other}")),
+        };
+        Ok(interval_result(credible_interval(&samples, level)))
+    });
+    m.insert(name, f);
+    let (name, f) = native("hdi", 2, |args| {
+        let samples = as_floats(&list_of_values(&args[0])?)?;
+        let level = match &args[1] {
+            Value::Float(x) => *x,
+            Value::Int(i) => *i as f64,
+            other => return Err(format!("expected a numeric level, found {other}")),
+        };
+        Ok(interval_result(hdi(&samples, level)))
+    });
+    m.insert(name, f);
+    let (name, f) = native("mean", 1, |args| {
+        let samples = as_floats(&list_of_values(&args[0])?)?;
+        if samples.is_empty() {
+            return Err("mean of an empty list is undefined".to_string());
+        }
+        Ok(Value::Float(mean(&samples)))
+    });
+    m.insert(name, f);
+    let (name, f) = native("summary", 1, |args| {
+        let values = list_of_values(&args[0])?;
+        match summary(&values) {
+            Some(table) => Ok(Value::Map(table)),
+            None => Ok(Value::Unit),
+        }
+    });
+    m.insert(name, f);
+    m
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hdi_roughly_matches_equal_tailed_interval_for_symmetric_data() {
+        // A triangular distribution peaked at 0: denser in the middle, so
+        // the HDI and the equal-tailed interval should land close together.
+        let mut samples = Vec::new();
+        for i in -50_i32..=50 {
+            let weight = 51 - i.abs();
+            samples.extend(std::iter::repeat_n(i as f64, weight as usize));
+        }
+
+        let (ci_lo, ci_hi) = credible_interval(&samples, 0.95).unwrap();
+        let (hdi_lo, hdi_hi) = hdi(&samples, 0.95).unwrap();
+        assert!((ci_lo - hdi_lo).abs() < 5.0, "ci=({ci_lo},{ci_hi}) hdi=({hdi_lo},{hdi_hi})");
+        assert!((ci_hi - hdi_hi).abs() < 5.0, "ci=({ci_lo},{ci_hi}) hdi=({hdi_lo},{hdi_hi})");
+    }
+
+    #[test]
+    fn online_stats_matches_a_single_shot_mean_and_variance() {
+        let samples = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0];
+
+        let mut online = OnlineStats::new();
+        for &x in &samples {
+            online.update(x);
+        }
+
+        let one_shot_mean = mean(&samples);
+        let one_shot_std = std_dev(&samples, one_shot_mean);
+        assert!((online.mean().unwrap() - one_shot_mean).abs() < 1e-9);
+        assert!((online.std().unwrap() - one_shot_std).abs() < 1e-9);
+    }
+
+    #[test]
+    fn online_stats_in_batches_matches_feeding_all_at_once() {
+        let samples = [3.0, 1.0, 4.0, 1.0, 5.0, 9.0, 2.0, 6.0];
+
+        let mut all_at_once = OnlineStats::new();
+        for &x in &samples {
+            all_at_once.update(x);
+        }
+
+        let mut batched = OnlineStats::new();
+        for batch in samples.chunks(3) {
+            for &x in batch {
+                batched.update(x);
+            }
+        }
+
+        assert_eq!(batched.count(), all_at_once.count());
+        assert!((batched.mean().unwrap() - all_at_once.mean().unwrap()).abs() < 1e-9);
+        assert!((batched.std().unwrap() - all_at_once.std().unwrap()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn online_stats_is_empty_before_any_update() {
+        let stats = OnlineStats::new();
+        assert_eq!(stats.count(), 0);
+        assert_eq!(stats.mean(), None);
+        assert_eq!(stats.std(), None);
+    }
+
+    #[test]
+    fn mean_native_averages_a_list_of_ints() {
+        let funcs = native_functions();
+        let mean_fn = &funcs["mean"];
+        let xs = Value::List(im::vector![Value::Int(1), Value::Int(2), Value::Int(3)]);
+        assert_eq!((mean_fn.func)(&[xs]), Ok(Value::Float(2.0)));
+    }
+
+    #[test]
+    fn summary_of_one_through_five_has_expected_median_and_mean() {
+        let values = vec![
+            Value::Int(1),
+            Value::Int(2),
+            Value::Int(3),
+            Value::Int(4),
+            Value::Int(5),
+        ];
+        let table = summary(&values).unwrap();
+        assert_eq!(table.get("mean"), Some(&Value::Float(3.0)));
+        assert_eq!(table.get("median"), Some(&Value::Float(3.0)));
+        assert_eq!(table.get("count"), Some(&Value::Int(5)));
+    }
+}
@@ -0,0 +1,233 @@
+//! String manipulation natives: case conversion, trimming, splitting and
+//! joining, substring search, replacement, and regex matching.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use regex::Regex;
+
+use crate::value::{NativeFunction, Value};
+
+/// Whether `s` contains a match for the regular expression `pattern`.
+pub fn regex_match(pattern: &str, s: &str) -> Result<bool, String> {
+    let re = Regex::new(pattern).map_err(|e| format!("invalid regex `{pattern}`: {e}"))?;
+    Ok(re.is_match(s))
+}
+
+/// The first substring of `s` that matches `pattern`, or `None` if there is
+/// no match.
+pub fn regex_find(pattern: &str, s: &str) -> Result<Option<String>, String> {
+    let re = Regex::new(pattern).map_err(|e| format!("invalid regex `{pattern}`: {e}"))?;
+    Ok(re.find(s).map(|m| m.as_str().to_string()))
+}
+
+/// Replaces every match of `pattern` in `s` with `repl`, which may reference
+/// capture groups as `$1`, `$name`, etc. (see [`regex::Regex::replace_all`]).
+pub fn regex_replace(pattern: &str, s: &str, repl: &str) -> Result<String, String> {
+    let re = Regex::new(pattern).map_err(|e| format!("invalid regex `{pattern}`: {e}"))?;
+    Ok(re.replace_all(s, repl).into_owned())
+}
+
+fn string_of(v: &Value) -> Result<String, String> {
+    match v {
+        Value::String(s) => Ok(s.clone()),
+        other => Err(format!("expected a String, found {other}")),
+    }
+}
+
+/// Splits `s` on every occurrence of `sep`, keeping empty fields (matching
+/// `str::split`'s behavior for e.g. a leading or trailing separator).
+pub fn split(s: &str, sep: &str) -> Vec<String> {
+    s.split(sep).map(|piece| piece.to_string()).collect()
+}
+
+/// Joins `parts` with `sep` between each pair, the inverse of [`split`].
+pub fn join(parts: &[String], sep: &str) -> String {
+    parts.join(sep)
+}
+
+fn native(name: &str, arity: usize, func: impl Fn(&[Value]) -> Result<Value, String> + Send + Sync + 'static) -> (String, NativeFunction) {
+    (
+        name.to_string(),
+        NativeFunction {
+            name: name.to_string(),
+            arity,
+            func: Arc::new(func),
+        },
+    )
+}
+
+pub fn native_functions() -> HashMap<String, NativeFunction> {
+    let mut m = HashMap::new();
+
+    let (name, f) = native("string_upper", 1, |args| Ok(Value::String(string_of(&args[0])?.to_uppercase())));
+    m.insert(name, f);
+
+    let (name, f) = native("string_lower", 1, |args| Ok(Value::String(string_of(&args[0])?.to_lowercase())));
+    m.insert(name, f);
+
+    let (name, f) = native("string_trim", 1, |args| Ok(Value::String(string_of(&args[0])?.trim().to_string())));
+    m.insert(name, f);
+
+    let (name, f) = native("string_length", 1, |args| Ok(Value::Int(string_of(&args[0])?.chars().count() as i64)));
+    m.insert(name, f);
+
+    let (name, f) = native("string_split", 2, |args| {
+        let s = string_of(&args[0])?;
+        let sep = string_of(&args[1])?;
+        Ok(Value::List(split(&s, &sep).into_iter().map(Value::String).collect()))
+    });
+    m.insert(name, f);
+
+    let (name, f) = native("string_join", 2, |args| {
+        let parts = match &args[0] {
+            Value::List(items) => items.iter().map(string_of).collect::<Result<Vec<_>, _>>()?,
+            other => return Err(format!("expected a List of Strings, found {other}")),
+        };
+        let sep = string_of(&args[1])?;
+        Ok(Value::String(join(&parts, &sep)))
+    });
+    m.insert(name, f);
+
+    let (name, f) = native("string_replace", 3, |args| {
+        let s = string_of(&args[0])?;
+        let from = string_of(&args[1])?;
+        let to = string_of(&args[2])?;
+        Ok(Value::String(s.replace(&from, &to)))
+    });
+    m.insert(name, f);
+
+    let (name, f) = native("string_contains", 2, |args| {
+        Ok(Value::Bool(string_of(&args[0])?.contains(&string_of(&args[1])?)))
+    });
+    m.insert(name, f);
+
+    let (name, f) = native("string_starts_with", 2, |args| {
+        Ok(Value::Bool(string_of(&args[0])?.starts_with(&string_of(&args[1])?)))
+    });
+    m.insert(name, f);
+
+    let (name, f) = native("string_ends_with", 2, |args| {
+        Ok(Value::Bool(string_of(&args[0])?.ends_with(&string_of(&args[1])?)))
+    });
+    m.insert(name, f);
+
+    let (name, f) = native("regex_match", 2, |args| {
+        let pattern = string_of(&args[0])?;
+        let s = string_of(&args[1])?;
+        match regex_match(&pattern, &s) {
+            Ok(matched) => Ok(Value::Bool(matched)),
+            Err(e) => Ok(Value::Error(e)),
+        }
+    });
+    m.insert(name, f);
+
+    let (name, f) = native("regex_find", 2, |args| {
+        let pattern = string_of(&args[0])?;
+        let s = string_of(&args[1])?;
+        match regex_find(&pattern, &s) {
+            Ok(Some(found)) => Ok(Value::String(found)),
+            Ok(None) => Ok(Value::Error(format!("no match for `{pattern}`"))),
+            Err(e) => Ok(Value::Error(e)),
+        }
+    });
+    m.insert(name, f);
+
+    let (name, f) = native("regex_replace", 3, |args| {
+        let pattern = string_of(&args[0])?;
+        let s = string_of(&args[1])?;
+        let repl = string_of(&args[2])?;
+        match regex_replace(&pattern, &s, &repl) {
+            Ok(replaced) => Ok(Value::String(replaced)),
+            Err(e) => Ok(Value::Error(e)),
+        }
+    });
+    m.insert(name, f);
+
+    m
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_keeps_empty_fields_around_separators() {
+        assert_eq!(split(",a,,b,", ","), vec!["", "a", "", "b", ""]);
+    }
+
+    #[test]
+    fn join_is_the_inverse_of_split() {
+        let parts = split("a,b,c", ",");
+        assert_eq!(join(&parts, ","), "a,b,c");
+    }
+
+    #[test]
+    fn string_upper_and_lower_round_trip_ascii_case() {
+        let funcs = native_functions();
+        let upper = funcs.get("string_upper").unwrap();
+        let lower = funcs.get("string_lower").unwrap();
+        assert_eq!((upper.func)(&[Value::String("Hello".into())]).unwrap(), Value::String("HELLO".into()));
+        assert_eq!((lower.func)(&[Value::String("Hello".into())]).unwrap(), Value::String("hello".into()));
+    }
+
+    #[test]
+    fn string_trim_strips_leading_and_trailing_whitespace() {
+        let funcs = native_functions();
+        let trim = funcs.get("string_trim").unwrap();
+        assert_eq!((trim.func)(&[Value::String("  hi  ".into())]).unwrap(), Value::String("hi".into()));
+    }
+
+    #[test]
+    fn string_length_counts_chars_not_bytes() {
+        let funcs = native_functions();
+        let length = funcs.get("string_length").unwrap();
+        assert_eq!((length.func)(&[Value::String("héllo".into())]).unwrap(), Value::Int(5));
+    }
+
+    #[test]
+    fn string_replace_substitutes_every_occurrence() {
+        let funcs = native_functions();
+        let replace = funcs.get("string_replace").unwrap();
+        let result = (replace.func)(&[Value::String("a-b-c".into()), Value::String("-".into()), Value::String("_".into())]).unwrap();
+        assert_eq!(result, Value::String("a_b_c".into()));
+    }
+
+    #[test]
+    fn regex_match_finds_a_pattern_anywhere_in_the_string() {
+        assert!(regex_match(r"\d+", "abc123").unwrap());
+        assert!(!regex_match(r"^\d+$", "abc123").unwrap());
+    }
+
+    #[test]
+    fn regex_find_returns_the_first_match_only() {
+        assert_eq!(regex_find(r"\d+", "a1 b22 c333").unwrap(), Some("1".to_string()));
+        assert_eq!(regex_find(r"\d+", "no digits here").unwrap(), None);
+    }
+
+    #[test]
+    fn regex_replace_substitutes_a_captured_group() {
+        let result = regex_replace(r"(\w+)@(\w+)", "user@host", "$2:$1").unwrap();
+        assert_eq!(result, "host:user");
+    }
+
+    #[test]
+    fn an_invalid_regex_pattern_is_a_value_error() {
+        let funcs = native_functions();
+        let regex_match = funcs.get("regex_match").unwrap();
+        let result = (regex_match.func)(&[Value::String("(".into()), Value::String("x".into())]).unwrap();
+        assert!(matches!(result, Value::Error(_)));
+    }
+
+    #[test]
+    fn string_contains_starts_with_and_ends_with() {
+        let funcs = native_functions();
+        let contains = funcs.get("string_contains").unwrap();
+        let starts = funcs.get("string_starts_with").unwrap();
+        let ends = funcs.get("string_ends_with").unwrap();
+        let s = Value::String("hello world".into());
+        assert_eq!((contains.func)(&[s.clone(), Value::String("o wo".into())]).unwrap(), Value::Bool(true));
+        assert_eq!((starts.func)(&[s.clone(), Value::String("hello".into())]).unwrap(), Value::Bool(true));
+        assert_eq!((ends.func)(&[s, Value::String("world".into())]).unwrap(), Value::Bool(true));
+    }
+}
@@ -0,0 +1,242 @@
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use bet_core::{Expr, Ternary};
+use im::{HashMap as ImMap, Vector as ImVector};
+
+use crate::random::Distribution;
+
+/// The signature every native betlang function implements.
+pub type NativeFn = dyn Fn(&[Value]) -> Result<Value, String> + Send + Sync;
+
+/// A native function callable from betlang: a name (for error messages)
+/// plus the Rust closure that implements it.
+#[derive(Clone)]
+pub struct NativeFunction {
+    pub name: String,
+    pub arity: usize,
+    pub func: Arc<NativeFn>,
+}
+
+/// A `fun x -> body` closed over the environment it was created in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Closure {
+    pub param: String,
+    pub body: Expr,
+    pub env: ValueEnv,
+}
+
+/// Variable bindings in scope during evaluation.
+pub type ValueEnv = HashMap<String, Value>;
+
+/// The runtime representation of every betlang value.
+///
+/// This is the single value type shared across the runtime crates
+/// (`bet-rt`, `bet-viz`, ...) and the tree-walking interpreter in
+/// `bet-eval`, so that `native_functions()` can be called directly from
+/// evaluated betlang code.
+#[derive(Clone)]
+pub enum Value {
+    Unit,
+    Bool(bool),
+    Ternary(Ternary),
+    Int(i64),
+    /// An integer too large to fit in `i64`, kept as `u64` so values like
+    /// 64-bit ids round-trip through JSON without losing precision.
+    UInt(u64),
+    Float(f64),
+    String(String),
+    List(ImVector<Value>),
+    Map(ImMap<String, Value>),
+    Set(ImMap<Value, ()>),
+    Tuple(Vec<Value>),
+    Bytes(Vec<u8>),
+    Dist(Arc<Distribution>),
+    Native(Arc<NativeFunction>),
+    /// An opened file path, kept around for natives that stream I/O.
+    File(String),
+    /// A `fun x -> body` closure produced by the interpreter.
+    Closure(Arc<Closure>),
+    Error(String),
+}
+
+impl Value {
+    /// Draws a single sample using the given RNG if this is a
+    /// [`Value::Dist`], for reproducible sequences when `rng` was seeded
+    /// (e.g. via [`crate::random::seeded_rng`]). `None` for anything else.
+    pub fn sample_with<R: rand::RngCore>(&self, rng: &mut R) -> Option<Value> {
+        match self {
+            Value::Dist(dist) => Some(dist.sample_with(rng)),
+            _ => None,
+        }
+    }
+
+    /// Draws `n` samples in sequence using the given RNG if this is a
+    /// [`Value::Dist`]. `None` for anything else.
+    pub fn sample_n_with<R: rand::RngCore>(&self, n: usize, rng: &mut R) -> Option<Vec<Value>> {
+        match self {
+            Value::Dist(dist) => Some(dist.sample_n_with(n, rng)),
+            _ => None,
+        }
+    }
+}
+
+/// Sorts `map`'s entries by key, so callers that serialize or display a
+/// [`Value::Map`] get a deterministic order regardless of `im::HashMap`'s
+/// (unspecified, hash-based) iteration order. Used anywhere a map's
+/// iteration order is externally visible: [`std::fmt::Display`], pretty
+/// printing, and JSON/MessagePack encoding.
+pub fn sorted_map_entries(map: &ImMap<String, Value>) -> Vec<(&String, &Value)> {
+    let mut entries: Vec<(&String, &Value)> = map.iter().collect();
+    entries.sort_by_key(|(k, _)| *k);
+    entries
+}
+
+impl std::fmt::Debug for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Value::Unit => write!(f, "()"),
+            Value::Bool(b) => write!(f, "{b}"),
+            Value::Ternary(Ternary::True) => write!(f, "true"),
+            Value::Ternary(Ternary::False) => write!(f, "false"),
+            Value::Ternary(Ternary::Unknown) => write!(f, "unknown"),
+            Value::Int(i) => write!(f, "{i}"),
+            Value::UInt(u) => write!(f, "{u}"),
+            Value::Float(x) => write!(f, "{x}"),
+            Value::String(s) => write!(f, "{s:?}"),
+            Value::List(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                write!(f, "]")
+            }
+            Value::Map(m) => {
+                write!(f, "{{")?;
+                for (i, (k, v)) in sorted_map_entries(m).into_iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{k}: {v}")?;
+                }
+                write!(f, "}}")
+            }
+            Value::Set(s) => {
+                write!(f, "{{")?;
+                for (i, (v, _)) in s.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{v}")?;
+                }
+                write!(f, "}}")
+            }
+            Value::Tuple(items) => {
+                write!(f, "(")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{item}")?;
+                }
+                write!(f, ")")
+            }
+            Value::Bytes(b) => write!(f, "<{} bytes>", b.len()),
+            Value::Dist(d) => write!(f, "<dist {}>", d.name),
+            Value::Native(n) => write!(f, "<native {}>", n.name),
+            Value::File(path) => write!(f, "<file {path}>"),
+            Value::Closure(_) => write!(f, "<closure>"),
+            Value::Error(msg) => write!(f, "<error: {msg}>"),
+        }
+    }
+}
+
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Value::Unit, Value::Unit) => true,
+            (Value::Bool(a), Value::Bool(b)) => a == b,
+            (Value::Ternary(a), Value::Ternary(b)) => a == b,
+            (Value::Int(a), Value::Int(b)) => a == b,
+            (Value::UInt(a), Value::UInt(b)) => a == b,
+            (Value::Float(a), Value::Float(b)) => a == b,
+            (Value::String(a), Value::String(b)) => a == b,
+            (Value::List(a), Value::List(b)) => a == b,
+            (Value::Map(a), Value::Map(b)) => a == b,
+            (Value::Set(a), Value::Set(b)) => a == b,
+            (Value::Tuple(a), Value::Tuple(b)) => a == b,
+            (Value::Bytes(a), Value::Bytes(b)) => a == b,
+            (Value::Dist(a), Value::Dist(b)) => Arc::ptr_eq(a, b),
+            (Value::Native(a), Value::Native(b)) => Arc::ptr_eq(a, b),
+            (Value::File(a), Value::File(b)) => a == b,
+            // Closures compare by identity, not by structure: there's no
+            // useful notion of two closures being "the same function".
+            (Value::Closure(a), Value::Closure(b)) => Arc::ptr_eq(a, b),
+            (Value::Error(a), Value::Error(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Value {}
+
+impl Hash for Value {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Value::Unit => {}
+            Value::Bool(b) => b.hash(state),
+            Value::Ternary(t) => (*t as u8).hash(state),
+            Value::Int(i) => i.hash(state),
+            Value::UInt(u) => u.hash(state),
+            Value::Float(x) => x.to_bits().hash(state),
+            Value::String(s) => s.hash(state),
+            Value::List(items) => items.iter().for_each(|v| v.hash(state)),
+            Value::Map(m) => {
+                for (k, v) in sorted_map_entries(m) {
+                    k.hash(state);
+                    v.hash(state);
+                }
+            }
+            Value::Set(s) => s.keys().for_each(|v| v.hash(state)),
+            Value::Tuple(items) => items.hash(state),
+            Value::Bytes(b) => b.hash(state),
+            Value::Dist(d) => Arc::as_ptr(d).hash(state),
+            Value::Native(n) => Arc::as_ptr(n).hash(state),
+            Value::File(p) => p.hash(state),
+            Value::Closure(c) => Arc::as_ptr(c).hash(state),
+            Value::Error(e) => e.hash(state),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn map_display_order_is_sorted_by_key_regardless_of_insertion_order() {
+        let mut forward = ImMap::new();
+        forward.insert("b".to_string(), Value::Int(2));
+        forward.insert("a".to_string(), Value::Int(1));
+        forward.insert("c".to_string(), Value::Int(3));
+
+        let mut reverse = ImMap::new();
+        reverse.insert("c".to_string(), Value::Int(3));
+        reverse.insert("a".to_string(), Value::Int(1));
+        reverse.insert("b".to_string(), Value::Int(2));
+
+        assert_eq!(Value::Map(forward).to_string(), "{a: 1, b: 2, c: 3}");
+        assert_eq!(Value::Map(reverse).to_string(), "{a: 1, b: 2, c: 3}");
+    }
+}
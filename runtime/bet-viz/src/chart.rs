@@ -0,0 +1,445 @@
+//! SVG line and scatter charts via `plotters`, with optional log10 axes for
+//! data spanning several orders of magnitude.
+
+use plotters::prelude::*;
+
+use crate::{PlotConfig, VizError, VizResult};
+
+fn map_draw_err<E: std::fmt::Display>(e: E) -> VizError {
+    VizError::InvalidData(e.to_string())
+}
+
+/// Checks that every value on a log axis is strictly positive, since
+/// `log(0)` and `log(negative)` have no point on the chart.
+fn validate_log_domain(points: &[(f64, f64)], config: &PlotConfig) -> VizResult<()> {
+    if config.x_log && points.iter().any(|&(x, _)| x <= 0.0) {
+        return Err(VizError::InvalidData("x_log requires every x value to be positive".to_string()));
+    }
+    if config.y_log && points.iter().any(|&(_, y)| y <= 0.0) {
+        return Err(VizError::InvalidData("y_log requires every y value to be positive".to_string()));
+    }
+    Ok(())
+}
+
+/// `(x, y)` with any log-enabled axis replaced by its log10, so the chart
+/// itself only ever needs to draw on a linear grid.
+fn to_plot_space(points: &[(f64, f64)], config: &PlotConfig) -> Vec<(f64, f64)> {
+    points.iter().map(|&p| point_to_plot_space(p, config)).collect()
+}
+
+fn point_to_plot_space((x, y): (f64, f64), config: &PlotConfig) -> (f64, f64) {
+    (if config.x_log { x.log10() } else { x }, if config.y_log { y.log10() } else { y })
+}
+
+fn axis_range(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    let (low, high) = values.fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| (lo.min(v), hi.max(v)));
+    if low < high {
+        (low, high)
+    } else {
+        (low - 0.5, high + 0.5)
+    }
+}
+
+/// The x and y axis ranges `plotted` needs, via [`axis_range`] on each
+/// coordinate independently. A single point, or every point sharing an x or
+/// y, still gets a sensible `±0.5`-padded range on that axis rather than a
+/// degenerate `build_cartesian_2d` call.
+fn compute_bounds(plotted: &[(f64, f64)]) -> ((f64, f64), (f64, f64)) {
+    (axis_range(plotted.iter().map(|&(x, _)| x)), axis_range(plotted.iter().map(|&(_, y)| y)))
+}
+
+fn render(points: &[(f64, f64)], config: &PlotConfig, draw_scatter: bool) -> VizResult<String> {
+    validate_log_domain(points, config)?;
+    let plotted = to_plot_space(points, config);
+    let ((x_min, x_max), (y_min, y_max)) = compute_bounds(&plotted);
+    let x_log = config.x_log;
+    let y_log = config.y_log;
+
+    let mut svg = String::new();
+    {
+        let root = SVGBackend::with_string(&mut svg, (config.width as u32, config.height as u32)).into_drawing_area();
+        root.fill(&WHITE).map_err(map_draw_err)?;
+
+        let mut chart = ChartBuilder::on(&root)
+            .margin(config.margin as u32)
+            .x_label_area_size(30)
+            .y_label_area_size(50)
+            .build_cartesian_2d(x_min..x_max, y_min..y_max)
+            .map_err(map_draw_err)?;
+
+        chart
+            .configure_mesh()
+            .x_label_formatter(&move |x| if x_log { format!("{:.2e}", 10f64.powf(*x)) } else { format!("{x:.2}") })
+            .y_label_formatter(&move |y| if y_log { format!("{:.2e}", 10f64.powf(*y)) } else { format!("{y:.2}") })
+            .draw()
+            .map_err(map_draw_err)?;
+
+        if draw_scatter {
+            chart.draw_series(plotted.iter().map(|&(x, y)| Circle::new((x, y), 3, BLUE.filled()))).map_err(map_draw_err)?;
+        } else {
+            chart.draw_series(LineSeries::new(plotted.iter().copied(), &BLUE)).map_err(map_draw_err)?;
+        }
+
+        draw_reference_lines(&mut chart, config, (x_min, x_max), (y_min, y_max))?;
+        draw_annotations(&mut chart, config)?;
+        root.present().map_err(map_draw_err)?;
+    }
+    Ok(svg)
+}
+
+/// Draws `config.hlines`/`config.vlines` spanning the chart's current axis
+/// range, each with its label at the line's start if non-empty.
+fn draw_reference_lines<DB, CT>(
+    chart: &mut ChartContext<DB, CT>,
+    config: &PlotConfig,
+    (x_min, x_max): (f64, f64),
+    (y_min, y_max): (f64, f64),
+) -> VizResult<()>
+where
+    DB: DrawingBackend,
+    CT: CoordTranslate<From = (f64, f64)>,
+{
+    for (y, label) in &config.hlines {
+        let y = if config.y_log { y.log10() } else { *y };
+        chart.draw_series(std::iter::once(PathElement::new([(x_min, y), (x_max, y)], BLACK))).map_err(map_draw_err)?;
+        if !label.is_empty() {
+            chart
+                .draw_series(std::iter::once(Text::new(label.clone(), (x_min, y), ("sans-serif", 12).into_font())))
+                .map_err(map_draw_err)?;
+        }
+    }
+    for (x, label) in &config.vlines {
+        let x = if config.x_log { x.log10() } else { *x };
+        chart.draw_series(std::iter::once(PathElement::new([(x, y_min), (x, y_max)], BLACK))).map_err(map_draw_err)?;
+        if !label.is_empty() {
+            chart
+                .draw_series(std::iter::once(Text::new(label.clone(), (x, y_min), ("sans-serif", 12).into_font())))
+                .map_err(map_draw_err)?;
+        }
+    }
+    Ok(())
+}
+
+/// Marks each of `config.annotations` with a small filled dot and its label,
+/// on top of whatever series was already drawn.
+fn draw_annotations<DB, CT>(chart: &mut ChartContext<DB, CT>, config: &PlotConfig) -> VizResult<()>
+where
+    DB: DrawingBackend,
+    CT: CoordTranslate<From = (f64, f64)>,
+{
+    for (x, y, label) in &config.annotations {
+        let point = point_to_plot_space((*x, *y), config);
+        chart.draw_series(std::iter::once(Circle::new(point, 4, BLACK.filled()))).map_err(map_draw_err)?;
+        chart
+            .draw_series(std::iter::once(Text::new(label.clone(), point, ("sans-serif", 12).into_font())))
+            .map_err(map_draw_err)?;
+    }
+    Ok(())
+}
+
+/// Renders `points` connected by straight line segments as a standalone SVG
+/// document. Errors with [`VizError::InvalidData`] if a log axis is
+/// requested over non-positive data.
+pub fn line_plot(points: &[(f64, f64)], config: &PlotConfig) -> VizResult<String> {
+    render(points, config, false)
+}
+
+/// Renders `points` as a scatter plot. Errors with [`VizError::InvalidData`]
+/// if a log axis is requested over non-positive data.
+pub fn scatter_plot(points: &[(f64, f64)], config: &PlotConfig) -> VizResult<String> {
+    render(points, config, true)
+}
+
+/// One series in a [`line_plot_dual`] plot: a label for its axis/legend and
+/// its `(x, y)` points.
+pub struct DualSeries<'a> {
+    pub label: &'a str,
+    pub points: &'a [(f64, f64)],
+}
+
+/// Renders `primary` against the left y-axis and `secondary` against an
+/// independently-scaled right y-axis, sharing one x-axis. Useful for
+/// comparing two series on very different scales. Returns a standalone SVG
+/// document.
+pub fn line_plot_dual(primary: &DualSeries, secondary: &DualSeries, config: &PlotConfig) -> VizResult<String> {
+    let (x_min, x_max) =
+        axis_range(primary.points.iter().chain(secondary.points.iter()).map(|&(x, _)| x));
+    let (y1_min, y1_max) = axis_range(primary.points.iter().map(|&(_, y)| y));
+    let (y2_min, y2_max) = axis_range(secondary.points.iter().map(|&(_, y)| y));
+
+    let mut svg = String::new();
+    {
+        let root = SVGBackend::with_string(&mut svg, (config.width as u32, config.height as u32)).into_drawing_area();
+        root.fill(&WHITE).map_err(map_draw_err)?;
+
+        let mut chart = ChartBuilder::on(&root)
+            .margin(config.margin as u32)
+            .x_label_area_size(30)
+            .y_label_area_size(50)
+            .right_y_label_area_size(50)
+            .build_cartesian_2d(x_min..x_max, y1_min..y1_max)
+            .map_err(map_draw_err)?
+            .set_secondary_coord(x_min..x_max, y2_min..y2_max);
+
+        chart.configure_mesh().y_desc(primary.label).draw().map_err(map_draw_err)?;
+        chart.configure_secondary_axes().y_desc(secondary.label).draw().map_err(map_draw_err)?;
+
+        chart
+            .draw_series(LineSeries::new(primary.points.iter().copied(), &BLUE))
+            .map_err(map_draw_err)?
+            .label(primary.label)
+            .legend(|(x, y)| PathElement::new([(x, y), (x + 20, y)], BLUE));
+        chart
+            .draw_secondary_series(LineSeries::new(secondary.points.iter().copied(), &RED))
+            .map_err(map_draw_err)?
+            .label(secondary.label)
+            .legend(|(x, y)| PathElement::new([(x, y), (x + 20, y)], RED));
+
+        chart.configure_series_labels().draw().map_err(map_draw_err)?;
+        root.present().map_err(map_draw_err)?;
+    }
+    Ok(svg)
+}
+
+/// One panel in a [`figure`]: which of the crate's plot kinds to render in
+/// it.
+pub enum PlotSpec {
+    Line(Vec<(f64, f64)>),
+    Scatter(Vec<(f64, f64)>),
+}
+
+/// Composes `panels` (each a `(title, spec)`) into a grid of `cols` columns
+/// (rows implied by `panels.len()`), via plotters' `split_evenly`. Each
+/// panel gets its own axes scaled to its own data, with `title` drawn above
+/// it. Returns a standalone SVG document.
+pub fn figure(panels: &[(String, PlotSpec)], cols: usize, config: &PlotConfig) -> VizResult<String> {
+    let cols = cols.max(1);
+    let rows = panels.len().div_ceil(cols).max(1);
+
+    let mut svg = String::new();
+    {
+        let root = SVGBackend::with_string(&mut svg, (config.width as u32, config.height as u32)).into_drawing_area();
+        root.fill(&WHITE).map_err(map_draw_err)?;
+        let areas = root.split_evenly((rows, cols));
+
+        for (area, (title, spec)) in areas.iter().zip(panels.iter()) {
+            let points = match spec {
+                PlotSpec::Line(pts) | PlotSpec::Scatter(pts) => pts,
+            };
+            let (x_min, x_max) = axis_range(points.iter().map(|&(x, _)| x));
+            let (y_min, y_max) = axis_range(points.iter().map(|&(_, y)| y));
+
+            let mut chart = ChartBuilder::on(area)
+                .caption(title, ("sans-serif", 14))
+                .margin(5)
+                .x_label_area_size(20)
+                .y_label_area_size(30)
+                .build_cartesian_2d(x_min..x_max, y_min..y_max)
+                .map_err(map_draw_err)?;
+            chart.configure_mesh().draw().map_err(map_draw_err)?;
+
+            match spec {
+                PlotSpec::Line(pts) => {
+                    chart.draw_series(LineSeries::new(pts.iter().copied(), &BLUE)).map_err(map_draw_err)?;
+                }
+                PlotSpec::Scatter(pts) => {
+                    chart.draw_series(pts.iter().map(|&(x, y)| Circle::new((x, y), 3, BLUE.filled()))).map_err(map_draw_err)?;
+                }
+            }
+        }
+        root.present().map_err(map_draw_err)?;
+    }
+    Ok(svg)
+}
+
+/// Renders `points` to a PNG-encoded byte buffer instead of SVG. Requires
+/// the `png` feature, which pulls in plotters' bitmap backend and the
+/// `image` crate for encoding.
+#[cfg(feature = "png")]
+fn render_png(points: &[(f64, f64)], config: &PlotConfig, draw_scatter: bool) -> VizResult<Vec<u8>> {
+    use plotters::prelude::BitMapBackend;
+
+    validate_log_domain(points, config)?;
+    let plotted = to_plot_space(points, config);
+    let ((x_min, x_max), (y_min, y_max)) = compute_bounds(&plotted);
+    let x_log = config.x_log;
+    let y_log = config.y_log;
+    let width = config.width as u32;
+    let height = config.height as u32;
+
+    let mut pixels = vec![0u8; width as usize * height as usize * 3];
+    {
+        let root = BitMapBackend::with_buffer(&mut pixels, (width, height)).into_drawing_area();
+        root.fill(&WHITE).map_err(map_draw_err)?;
+
+        let mut chart = ChartBuilder::on(&root)
+            .margin(config.margin as u32)
+            .x_label_area_size(30)
+            .y_label_area_size(50)
+            .build_cartesian_2d(x_min..x_max, y_min..y_max)
+            .map_err(map_draw_err)?;
+
+        chart
+            .configure_mesh()
+            .x_label_formatter(&move |x| if x_log { format!("{:.2e}", 10f64.powf(*x)) } else { format!("{x:.2}") })
+            .y_label_formatter(&move |y| if y_log { format!("{:.2e}", 10f64.powf(*y)) } else { format!("{y:.2}") })
+            .draw()
+            .map_err(map_draw_err)?;
+
+        if draw_scatter {
+            chart.draw_series(plotted.iter().map(|&(x, y)| Circle::new((x, y), 3, BLUE.filled()))).map_err(map_draw_err)?;
+        } else {
+            chart.draw_series(LineSeries::new(plotted.iter().copied(), &BLUE)).map_err(map_draw_err)?;
+        }
+
+        draw_reference_lines(&mut chart, config, (x_min, x_max), (y_min, y_max))?;
+        draw_annotations(&mut chart, config)?;
+        root.present().map_err(map_draw_err)?;
+    }
+
+    let img = image::RgbImage::from_raw(width, height, pixels)
+        .ok_or_else(|| VizError::InvalidData("rendered pixel buffer did not match the requested dimensions".to_string()))?;
+    let mut png_bytes = Vec::new();
+    img.write_to(&mut std::io::Cursor::new(&mut png_bytes), image::ImageOutputFormat::Png).map_err(map_draw_err)?;
+    Ok(png_bytes)
+}
+
+/// PNG-encoded equivalent of [`line_plot`]. Requires the `png` feature.
+#[cfg(feature = "png")]
+pub fn line_plot_png(points: &[(f64, f64)], config: &PlotConfig) -> VizResult<Vec<u8>> {
+    render_png(points, config, false)
+}
+
+/// PNG-encoded equivalent of [`scatter_plot`]. Requires the `png` feature.
+#[cfg(feature = "png")]
+pub fn scatter_plot_png(points: &[(f64, f64)], config: &PlotConfig) -> VizResult<Vec<u8>> {
+    render_png(points, config, true)
+}
+
+/// Writes `bytes` to `path`, creating any missing parent directories first
+/// (a common annoyance when writing to e.g. `plots/out.svg`).
+pub(crate) fn write_with_parents(path: &str, bytes: &[u8]) -> VizResult<()> {
+    if let Some(parent) = std::path::Path::new(path).parent() {
+        if !parent.as_os_str().is_empty() {
+            std::fs::create_dir_all(parent).map_err(|e| VizError::IoError(e.to_string()))?;
+        }
+    }
+    std::fs::write(path, bytes).map_err(|e| VizError::IoError(e.to_string()))
+}
+
+/// Writes an SVG document (as returned by [`line_plot`], [`scatter_plot`],
+/// or any of the crate's other SVG builders) to `path`.
+pub fn save_svg(svg: &str, path: &str) -> VizResult<()> {
+    write_with_parents(path, svg.as_bytes())
+}
+
+/// Writes PNG-encoded `bytes` (as returned by [`line_plot_png`] or
+/// [`scatter_plot_png`]) to `path`.
+#[cfg(feature = "png")]
+pub fn save_png(bytes: &[u8], path: &str) -> VizResult<()> {
+    write_with_parents(path, bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_plot_of_linear_data_produces_an_svg_document() {
+        let points = [(0.0, 0.0), (1.0, 1.0), (2.0, 2.0)];
+        let svg = line_plot(&points, &PlotConfig::default()).unwrap();
+        assert!(svg.starts_with("<?xml") || svg.starts_with("<svg"));
+    }
+
+    #[test]
+    fn a_log_y_axis_over_several_orders_of_magnitude_succeeds() {
+        let points = [(1.0, 1.0), (2.0, 10.0), (3.0, 100.0), (4.0, 1000.0)];
+        let config = PlotConfig { y_log: true, ..PlotConfig::default() };
+        assert!(line_plot(&points, &config).is_ok());
+    }
+
+    #[test]
+    fn a_log_axis_over_non_positive_data_is_rejected() {
+        let points = [(1.0, -5.0), (2.0, 10.0)];
+        let config = PlotConfig { y_log: true, ..PlotConfig::default() };
+        assert_eq!(
+            line_plot(&points, &config),
+            Err(VizError::InvalidData("y_log requires every y value to be positive".to_string()))
+        );
+    }
+
+    #[test]
+    fn scatter_plot_of_linear_data_produces_an_svg_document() {
+        let points = [(0.0, 3.0), (1.0, 1.0), (2.0, 4.0)];
+        let svg = scatter_plot(&points, &PlotConfig::default()).unwrap();
+        assert!(svg.starts_with("<?xml") || svg.starts_with("<svg"));
+    }
+
+    #[test]
+    fn an_annotation_labels_its_point_on_the_rendered_svg() {
+        let points = [(0.0, 0.0), (1.0, 1.0), (2.0, 4.0)];
+        let config = PlotConfig { annotations: vec![(1.0, 1.0, "midpoint".to_string())], ..PlotConfig::default() };
+        let svg = line_plot(&points, &config).unwrap();
+        assert!(svg.contains("midpoint"), "missing annotation label:\n{svg}");
+    }
+
+    #[test]
+    fn a_single_point_still_produces_a_well_formed_svg() {
+        let svg = line_plot(&[(1.0, 1.0)], &PlotConfig::default()).unwrap();
+        assert!(svg.starts_with("<?xml") || svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>\n") || svg.ends_with("</svg>"));
+    }
+
+    #[test]
+    fn a_vertical_line_of_identical_x_values_still_produces_a_well_formed_svg() {
+        let points = [(1.0, 0.0), (1.0, 1.0), (1.0, 2.0)];
+        let svg = scatter_plot(&points, &PlotConfig::default()).unwrap();
+        assert!(svg.starts_with("<?xml") || svg.starts_with("<svg"));
+    }
+
+    #[test]
+    fn an_hline_at_zero_is_labeled_in_the_rendered_svg() {
+        let points = [(0.0, -1.0), (1.0, 0.0), (2.0, 1.0)];
+        let config = PlotConfig { hlines: vec![(0.0, "zero".to_string())], ..PlotConfig::default() };
+        let svg = line_plot(&points, &config).unwrap();
+        assert!(svg.contains("zero"), "missing hline label:\n{svg}");
+    }
+
+    #[test]
+    fn a_two_panel_figure_includes_both_panel_titles() {
+        let panels = vec![
+            ("left".to_string(), PlotSpec::Line(vec![(0.0, 0.0), (1.0, 1.0)])),
+            ("right".to_string(), PlotSpec::Scatter(vec![(0.0, 1.0), (1.0, 0.0)])),
+        ];
+        let svg = figure(&panels, 2, &PlotConfig::default()).unwrap();
+        assert!(svg.contains("left"), "missing left panel title:\n{svg}");
+        assert!(svg.contains("right"), "missing right panel title:\n{svg}");
+    }
+
+    #[test]
+    fn save_svg_creates_missing_parent_directories() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("out.svg");
+        let svg = line_plot(&[(0.0, 0.0), (1.0, 1.0)], &PlotConfig::default()).unwrap();
+
+        save_svg(&svg, path.to_str().unwrap()).unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), svg);
+    }
+
+    #[test]
+    fn line_plot_dual_labels_both_axes() {
+        let primary = DualSeries { label: "temperature (C)", points: &[(0.0, 10.0), (1.0, 12.0), (2.0, 11.0)] };
+        let secondary = DualSeries { label: "pressure (kPa)", points: &[(0.0, 1000.0), (1.0, 1010.0), (2.0, 990.0)] };
+        let svg = line_plot_dual(&primary, &secondary, &PlotConfig::default()).unwrap();
+        assert!(svg.contains("temperature"), "missing primary axis label:\n{svg}");
+        assert!(svg.contains("pressure"), "missing secondary axis label:\n{svg}");
+    }
+
+    #[test]
+    #[cfg(feature = "png")]
+    fn line_plot_png_starts_with_the_png_magic_header() {
+        let points = [(0.0, 0.0), (1.0, 1.0), (2.0, 2.0)];
+        let bytes = line_plot_png(&points, &PlotConfig::default()).unwrap();
+        assert_eq!(&bytes[..8], &[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]);
+    }
+}
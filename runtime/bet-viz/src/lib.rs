@@ -0,0 +1,1038 @@
+//! `bet-viz` turns raw samples into plot data and renders it: line/scatter
+//! charts to SVG via `plotters`, histogram/bar/box/heatmap charts as
+//! hand-built SVG, braille-canvas charts to a terminal string via
+//! `textplots`.
+
+pub mod chart;
+pub mod native;
+pub mod term;
+
+use thiserror::Error;
+
+/// Errors producing a plot.
+#[derive(Debug, Clone, Error, PartialEq)]
+pub enum VizError {
+    #[error("group \"{0}\" has no samples to summarize")]
+    EmptyGroup(String),
+    #[error("invalid plot data: {0}")]
+    InvalidData(String),
+    #[error("I/O error: {0}")]
+    IoError(String),
+}
+
+pub type VizResult<T> = Result<T, VizError>;
+
+/// One bar of a histogram: its center x-value and height.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bar {
+    pub center: f64,
+    pub height: f64,
+}
+
+/// A histogram over `samples`, bucketed into `bins` equal-width bins
+/// spanning the samples' range. Bar heights are raw counts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    pub bars: Vec<Bar>,
+    pub bin_width: f64,
+}
+
+/// Buckets `samples` into `bins` equal-width bins. A single unique value
+/// (or no bin width to speak of) collapses to one bar rather than dividing
+/// by a zero-width bin.
+pub fn histogram(samples: &[f64], bins: usize) -> Histogram {
+    let bins = bins.max(1);
+    let (Some(low), Some(high)) = (
+        samples.iter().cloned().reduce(f64::min),
+        samples.iter().cloned().reduce(f64::max),
+    ) else {
+        return Histogram { bars: Vec::new(), bin_width: 0.0 };
+    };
+    if low == high {
+        return Histogram {
+            bars: vec![Bar { center: low, height: samples.len() as f64 }],
+            bin_width: 0.0,
+        };
+    }
+
+    let bin_width = (high - low) / bins as f64;
+    let mut counts = vec![0u32; bins];
+    for &x in samples {
+        let idx = (((x - low) / bin_width) as usize).min(bins - 1);
+        counts[idx] += 1;
+    }
+    let bars = counts
+        .iter()
+        .enumerate()
+        .map(|(i, &count)| Bar {
+            center: low + bin_width * (i as f64 + 0.5),
+            height: count as f64,
+        })
+        .collect();
+    Histogram { bars, bin_width }
+}
+
+/// A density plot built from [`histogram`]: bar heights are normalized so
+/// that `height * bin_width` summed over all bars integrates to ~1, with an
+/// optional fitted-normal overlay line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DistributionPlot {
+    pub bars: Vec<Bar>,
+    pub bin_width: f64,
+    pub y_label: String,
+    /// `(x, density)` points along a fitted normal curve, present only when
+    /// `show_fit` was set and there were enough samples to estimate a
+    /// mean/std from.
+    pub fit: Option<Vec<(f64, f64)>>,
+}
+
+/// Builds a normalized density plot over `samples`, bucketed into `bins`
+/// bins, with an optional fitted-normal overlay when `show_fit` is true.
+pub fn distribution_plot(samples: &[f64], bins: usize, show_fit: bool) -> DistributionPlot {
+    let hist = histogram(samples, bins);
+    let total = samples.len() as f64;
+
+    let bars = if hist.bin_width > 0.0 && total > 0.0 {
+        hist.bars
+            .iter()
+            .map(|b| Bar { center: b.center, height: b.height / (total * hist.bin_width) })
+            .collect()
+    } else {
+        // A single unique value (or no samples at all): there's no bin
+        // width to divide by, so the "density" is just a unit spike.
+        hist.bars
+            .iter()
+            .map(|b| Bar { center: b.center, height: if b.height > 0.0 { 1.0 } else { 0.0 } })
+            .collect()
+    };
+
+    let fit = show_fit.then(|| fitted_normal_curve(samples)).flatten();
+
+    DistributionPlot { bars, bin_width: hist.bin_width, y_label: "Density".to_string(), fit }
+}
+
+/// A histogram bin's `(start, end, count)`, as returned alongside the
+/// rendered SVG by [`histogram_with_data`].
+pub type HistogramBin = (f64, f64, u32);
+
+/// Renders `samples` as a bar-chart SVG via [`bar_chart`], alongside the
+/// `(bin_start, bin_end, count)` triples the bars were computed from, so
+/// callers can feed the same bucketing into downstream analysis without
+/// recomputing it.
+pub fn histogram_with_data(samples: &[f64], bins: usize, config: &PlotConfig) -> VizResult<(String, Vec<HistogramBin>)> {
+    let hist = histogram(samples, bins);
+    let half_width = hist.bin_width / 2.0;
+    let triples: Vec<HistogramBin> =
+        hist.bars.iter().map(|b| (b.center - half_width, b.center + half_width, b.height as u32)).collect();
+    let labels: Vec<String> = hist.bars.iter().map(|b| format!("{:.2}", b.center)).collect();
+    let values: Vec<f64> = hist.bars.iter().map(|b| b.height).collect();
+
+    let svg = bar_chart(&labels, &values, None, config)?;
+    Ok((svg, triples))
+}
+
+/// 200 points of a normal density curve fitted to `samples`' mean and
+/// standard deviation, spanning 4 standard deviations either side of the
+/// mean. `None` if there are too few samples, or no spread, to fit.
+fn fitted_normal_curve(samples: &[f64]) -> Option<Vec<(f64, f64)>> {
+    let n = samples.len();
+    if n < 2 {
+        return None;
+    }
+    let mean = samples.iter().sum::<f64>() / n as f64;
+    let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n as f64;
+    let std = variance.sqrt();
+    if std == 0.0 {
+        return None;
+    }
+
+    const POINTS: usize = 200;
+    let span = 4.0 * std;
+    Some(
+        (0..POINTS)
+            .map(|i| {
+                let x = mean - span + span * 2.0 * i as f64 / (POINTS - 1) as f64;
+                let z = (x - mean) / std;
+                let density = (-0.5 * z * z).exp() / (std * (2.0 * std::f64::consts::PI).sqrt());
+                (x, density)
+            })
+            .collect(),
+    )
+}
+
+/// A fixed palette cycled across series in multi-series plots like
+/// [`multi_distribution_plot`].
+const PALETTE: [&str; 6] = ["#1f77b4", "#ff7f0e", "#2ca02c", "#d62728", "#9467bd", "#8c564b"];
+
+const SVG_WIDTH: f64 = 640.0;
+const SVG_HEIGHT: f64 = 400.0;
+const PLOT_MARGIN: f64 = 40.0;
+const LEGEND_LINE_HEIGHT: f64 = 18.0;
+
+/// Like [`histogram`], but bucketed over an explicit `[low, high]` range
+/// rather than the samples' own min/max, so multiple datasets can share one
+/// x-axis.
+fn histogram_with_range(samples: &[f64], bins: usize, low: f64, high: f64) -> Histogram {
+    let bins = bins.max(1);
+    if low >= high || samples.is_empty() {
+        return Histogram { bars: Vec::new(), bin_width: 0.0 };
+    }
+    let bin_width = (high - low) / bins as f64;
+    let mut counts = vec![0u32; bins];
+    for &x in samples {
+        let idx = (((x - low) / bin_width) as usize).min(bins - 1);
+        counts[idx] += 1;
+    }
+    let bars = counts
+        .iter()
+        .enumerate()
+        .map(|(i, &count)| Bar {
+            center: low + bin_width * (i as f64 + 0.5),
+            height: count as f64,
+        })
+        .collect();
+    Histogram { bars, bin_width }
+}
+
+/// Overlays every `(name, samples)` series in `distributions` as a
+/// semi-transparent histogram sharing one x-range (the global min/max
+/// across every series), with a distinct palette color per series and a
+/// legend listing each series' name. Returns a standalone SVG document.
+pub fn multi_distribution_plot(distributions: &[(String, Vec<f64>)], bins: usize) -> String {
+    let all_samples: Vec<f64> = distributions.iter().flat_map(|(_, s)| s.iter().copied()).collect();
+    let (low, high) = match (
+        all_samples.iter().copied().reduce(f64::min),
+        all_samples.iter().copied().reduce(f64::max),
+    ) {
+        (Some(low), Some(high)) if low < high => (low, high),
+        (Some(low), _) => (low - 0.5, low + 0.5),
+        _ => (0.0, 1.0),
+    };
+
+    let histograms: Vec<(&str, Histogram)> = distributions
+        .iter()
+        .map(|(name, samples)| (name.as_str(), histogram_with_range(samples, bins, low, high)))
+        .collect();
+    let max_height = histograms
+        .iter()
+        .flat_map(|(_, h)| h.bars.iter().map(|b| b.height))
+        .fold(0.0_f64, f64::max)
+        .max(1.0);
+
+    let legend_height = LEGEND_LINE_HEIGHT * distributions.len() as f64;
+    let plot_width = SVG_WIDTH - 2.0 * PLOT_MARGIN;
+    let plot_height = SVG_HEIGHT - 2.0 * PLOT_MARGIN - legend_height;
+    let sx = plot_width / (high - low);
+    let sy = plot_height / max_height;
+
+    let mut svg = format!(r#"<svg xmlns="http://www.w3.org/2000/svg" width="{SVG_WIDTH}" height="{SVG_HEIGHT}">"#);
+
+    for (i, (_, hist)) in histograms.iter().enumerate() {
+        let color = PALETTE[i % PALETTE.len()];
+        for bar in &hist.bars {
+            let x = PLOT_MARGIN + (bar.center - hist.bin_width / 2.0 - low) * sx;
+            let bar_height = bar.height * sy;
+            let y = PLOT_MARGIN + (plot_height - bar_height);
+            let w = hist.bin_width * sx;
+            svg.push_str(&format!(
+                r#"<rect x="{x:.2}" y="{y:.2}" width="{w:.2}" height="{bar_height:.2}" fill="{color}" fill-opacity="0.5"/>"#
+            ));
+        }
+    }
+
+    for (i, (name, _)) in histograms.iter().enumerate() {
+        let color = PALETTE[i % PALETTE.len()];
+        let y = PLOT_MARGIN + plot_height + LEGEND_LINE_HEIGHT * (i as f64 + 1.0);
+        svg.push_str(&format!(
+            r#"<rect x="{PLOT_MARGIN:.2}" y="{:.2}" width="10" height="10" fill="{color}"/>"#,
+            y - 10.0
+        ));
+        svg.push_str(&format!(r#"<text x="{:.2}" y="{y:.2}" font-size="12">{name}</text>"#, PLOT_MARGIN + 16.0));
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+/// The five-number summary of one group, plus any points outside
+/// `1.5 * IQR` of the box, as used by [`box_plot`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoxStats {
+    pub min: f64,
+    pub q1: f64,
+    pub median: f64,
+    pub q3: f64,
+    pub max: f64,
+    pub outliers: Vec<f64>,
+}
+
+impl BoxStats {
+    /// Alias for [`box_stats`], for callers that prefer the associated-function
+    /// spelling.
+    pub fn from_data(samples: &[f64]) -> Option<BoxStats> {
+        box_stats(samples)
+    }
+}
+
+/// Type-7 (NumPy-default) percentile: linear interpolation between order
+/// statistics. `sorted` must already be sorted ascending and non-empty.
+pub(crate) fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let h = p / 100.0 * (sorted.len() - 1) as f64;
+    let lo = h.floor() as usize;
+    let hi = h.ceil() as usize;
+    if lo == hi {
+        sorted[lo]
+    } else {
+        sorted[lo] + (h - lo as f64) * (sorted[hi] - sorted[lo])
+    }
+}
+
+/// Summarizes `samples` into a [`BoxStats`]: quartiles via linear
+/// interpolation, and outliers beyond `1.5 * IQR` of the box split out from
+/// `min`/`max` (which then refer to the whiskers' ends). `None` for an
+/// empty slice.
+pub fn box_stats(samples: &[f64]) -> Option<BoxStats> {
+    if samples.is_empty() {
+        return None;
+    }
+    let mut sorted: Vec<f64> = samples.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    let q1 = percentile(&sorted, 25.0);
+    let median = percentile(&sorted, 50.0);
+    let q3 = percentile(&sorted, 75.0);
+    let iqr = q3 - q1;
+    let lower_fence = q1 - 1.5 * iqr;
+    let upper_fence = q3 + 1.5 * iqr;
+
+    let (mut inliers, mut outliers): (Vec<f64>, Vec<f64>) =
+        sorted.iter().partition(|&&x| x >= lower_fence && x <= upper_fence);
+    outliers.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    if inliers.is_empty() {
+        // Every point was fenced out (can happen when the IQR is 0): fall
+        // back to the full range rather than reporting an empty box.
+        inliers = sorted.clone();
+        outliers.clear();
+    }
+
+    Some(BoxStats {
+        min: inliers.first().copied().unwrap(),
+        q1,
+        median,
+        q3,
+        max: inliers.last().copied().unwrap(),
+        outliers,
+    })
+}
+
+/// Layout knobs for [`box_plot`]. `Default` matches the fixed dimensions
+/// [`multi_distribution_plot`] used before this became configurable.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlotConfig {
+    pub width: f64,
+    pub height: f64,
+    pub margin: f64,
+    /// Plot the x-axis on a log10 scale. Requires every x value to be
+    /// positive; see [`chart::line_plot`]/[`chart::scatter_plot`].
+    pub x_log: bool,
+    /// Plot the y-axis on a log10 scale. Requires every y value to be
+    /// positive; see [`chart::line_plot`]/[`chart::scatter_plot`].
+    pub y_log: bool,
+    /// Points to mark on top of the data in [`chart::line_plot`]/
+    /// [`chart::scatter_plot`], each as `(x, y, label)` in the same data
+    /// space as the plotted points (pre-log-transform).
+    pub annotations: Vec<(f64, f64, String)>,
+    /// Color gradient [`heatmap`] maps normalized values through.
+    pub color_map: ColorMap,
+    /// Horizontal reference lines drawn across [`chart::line_plot`]/
+    /// [`chart::scatter_plot`], each as `(y, label)`. An empty label draws
+    /// the line without text, e.g. for marking a mean.
+    pub hlines: Vec<(f64, String)>,
+    /// Vertical reference lines drawn across [`chart::line_plot`]/
+    /// [`chart::scatter_plot`], each as `(x, label)`.
+    pub vlines: Vec<(f64, String)>,
+}
+
+impl Default for PlotConfig {
+    fn default() -> Self {
+        PlotConfig {
+            width: SVG_WIDTH,
+            height: SVG_HEIGHT,
+            margin: PLOT_MARGIN,
+            x_log: false,
+            y_log: false,
+            annotations: Vec::new(),
+            color_map: ColorMap::default(),
+            hlines: Vec::new(),
+            vlines: Vec::new(),
+        }
+    }
+}
+
+/// Color gradient used by [`heatmap`] to map a normalized value in `[0, 1]`
+/// to an RGB color. `BlueRed` is the original white-to-blue ramp; the others
+/// are coarse (few-stop) approximations of their namesakes, good enough for
+/// a quick visual read without pulling in a colormap dependency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorMap {
+    #[default]
+    BlueRed,
+    Viridis,
+    Magma,
+    Grayscale,
+}
+
+const VIRIDIS_STOPS: [(f64, (u8, u8, u8)); 5] = [
+    (0.0, (68, 1, 84)),
+    (0.25, (59, 82, 139)),
+    (0.5, (33, 145, 140)),
+    (0.75, (94, 201, 98)),
+    (1.0, (253, 231, 37)),
+];
+
+const MAGMA_STOPS: [(f64, (u8, u8, u8)); 5] = [
+    (0.0, (0, 0, 4)),
+    (0.25, (81, 18, 124)),
+    (0.5, (183, 55, 121)),
+    (0.75, (252, 137, 97)),
+    (1.0, (252, 253, 191)),
+];
+
+/// Linearly interpolates the RGB channels between the two stops bracketing
+/// `t`. `stops` must be sorted ascending by its first element and span
+/// `[0.0, 1.0]`.
+fn lerp_stops(t: f64, stops: &[(f64, (u8, u8, u8))]) -> (u8, u8, u8) {
+    let t = t.clamp(0.0, 1.0);
+    let pair = stops.windows(2).find(|w| t <= w[1].0).unwrap_or(&stops[stops.len() - 2..]);
+    let (lo_t, (lr, lg, lb)) = pair[0];
+    let (hi_t, (hr, hg, hb)) = pair[1];
+    let local = if hi_t > lo_t { (t - lo_t) / (hi_t - lo_t) } else { 0.0 };
+    let lerp = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * local).round() as u8;
+    (lerp(lr, hr), lerp(lg, hg), lerp(lb, hb))
+}
+
+impl ColorMap {
+    fn color(self, t: f64) -> (u8, u8, u8) {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            ColorMap::BlueRed => {
+                let intensity = (t * 255.0).round() as u8;
+                (255 - intensity, 255 - intensity, 255)
+            }
+            ColorMap::Grayscale => {
+                let v = (t * 255.0).round() as u8;
+                (v, v, v)
+            }
+            ColorMap::Viridis => lerp_stops(t, &VIRIDIS_STOPS),
+            ColorMap::Magma => lerp_stops(t, &MAGMA_STOPS),
+        }
+    }
+}
+
+const BOX_WIDTH_FRACTION: f64 = 0.6;
+
+/// Renders one box-and-whiskers per `(name, samples)` group side by side,
+/// sharing a y-axis scaled to every group's full range (box, whiskers, and
+/// outliers). Returns a standalone SVG document, or [`VizError::EmptyGroup`]
+/// if any group has no samples.
+pub fn box_plot(groups: &[(String, Vec<f64>)], config: &PlotConfig) -> VizResult<String> {
+    let stats: Vec<(&str, BoxStats)> = groups
+        .iter()
+        .map(|(name, samples)| {
+            box_stats(samples).map(|s| (name.as_str(), s)).ok_or_else(|| VizError::EmptyGroup(name.clone()))
+        })
+        .collect::<VizResult<Vec<_>>>()?;
+
+    let (y_low, y_high) = stats
+        .iter()
+        .flat_map(|(_, s)| s.outliers.iter().copied().chain([s.min, s.max]))
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), x| (lo.min(x), hi.max(x)));
+    let (y_low, y_high) = if y_low < y_high { (y_low, y_high) } else { (y_low - 0.5, y_high + 0.5) };
+
+    let plot_width = config.width - 2.0 * config.margin;
+    let plot_height = config.height - 2.0 * config.margin;
+    let slot_width = plot_width / stats.len() as f64;
+    let box_width = slot_width * BOX_WIDTH_FRACTION;
+    let sy = plot_height / (y_high - y_low);
+    let y_of = |value: f64| config.margin + (plot_height - (value - y_low) * sy);
+
+    let width = config.width;
+    let height = config.height;
+    let mut svg = format!(r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}">"#);
+
+    for (i, (name, s)) in stats.iter().enumerate() {
+        let color = PALETTE[i % PALETTE.len()];
+        let cx = config.margin + slot_width * (i as f64 + 0.5);
+        let box_x = cx - box_width / 2.0;
+
+        svg.push_str(&format!(r#"<line x1="{cx:.2}" y1="{:.2}" x2="{cx:.2}" y2="{:.2}" stroke="{color}"/>"#, y_of(s.max), y_of(s.q3)));
+        svg.push_str(&format!(r#"<line x1="{cx:.2}" y1="{:.2}" x2="{cx:.2}" y2="{:.2}" stroke="{color}"/>"#, y_of(s.q1), y_of(s.min)));
+
+        let box_y = y_of(s.q3);
+        let box_height = y_of(s.q1) - y_of(s.q3);
+        svg.push_str(&format!(
+            r#"<rect x="{box_x:.2}" y="{box_y:.2}" width="{box_width:.2}" height="{box_height:.2}" fill="{color}" fill-opacity="0.5" stroke="{color}"/>"#
+        ));
+
+        let median_y = y_of(s.median);
+        svg.push_str(&format!(
+            r#"<line x1="{box_x:.2}" y1="{median_y:.2}" x2="{:.2}" y2="{median_y:.2}" stroke="{color}"/>"#,
+            box_x + box_width
+        ));
+
+        for &outlier in &s.outliers {
+            svg.push_str(&format!(r#"<circle cx="{cx:.2}" cy="{:.2}" r="3" fill="{color}"/>"#, y_of(outlier)));
+        }
+
+        svg.push_str(&format!(
+            r#"<text x="{cx:.2}" y="{:.2}" font-size="12" text-anchor="middle">{name}</text>"#,
+            config.height - config.margin / 2.0
+        ));
+    }
+
+    svg.push_str("</svg>");
+    Ok(svg)
+}
+
+/// Renders one bar per `(label, value)` pair, with an optional vertical
+/// error-bar whisker (`value ± errors[i]`) centered on each bar.
+/// [`VizError::InvalidData`] if `errors` is present but doesn't have one
+/// entry per value.
+pub fn bar_chart(labels: &[String], values: &[f64], errors: Option<&[f64]>, config: &PlotConfig) -> VizResult<String> {
+    if let Some(errors) = errors {
+        if errors.len() != values.len() {
+            return Err(VizError::InvalidData(format!(
+                "errors has {} entries but values has {}",
+                errors.len(),
+                values.len()
+            )));
+        }
+    }
+
+    let (y_low, y_high) = values
+        .iter()
+        .enumerate()
+        .flat_map(|(i, &v)| {
+            let err = errors.map(|e| e[i]).unwrap_or(0.0);
+            [v - err, v + err]
+        })
+        .chain([0.0])
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), x| (lo.min(x), hi.max(x)));
+    let (y_low, y_high) = if y_low < y_high { (y_low, y_high) } else { (y_low - 0.5, y_high + 0.5) };
+
+    let plot_width = config.width - 2.0 * config.margin;
+    let plot_height = config.height - 2.0 * config.margin;
+    let slot_width = plot_width / values.len().max(1) as f64;
+    let bar_width = slot_width * BOX_WIDTH_FRACTION;
+    let sy = plot_height / (y_high - y_low);
+    let y_of = |value: f64| config.margin + (plot_height - (value - y_low) * sy);
+
+    let width = config.width;
+    let height = config.height;
+    let mut svg = format!(r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}">"#);
+
+    for (i, (&value, label)) in values.iter().zip(labels.iter()).enumerate() {
+        let color = PALETTE[i % PALETTE.len()];
+        let cx = config.margin + slot_width * (i as f64 + 0.5);
+        let bar_x = cx - bar_width / 2.0;
+        let bar_top = y_of(value.max(0.0));
+        let bar_bottom = y_of(value.min(0.0));
+        svg.push_str(&format!(
+            r#"<rect x="{bar_x:.2}" y="{bar_top:.2}" width="{bar_width:.2}" height="{:.2}" fill="{color}"/>"#,
+            bar_bottom - bar_top
+        ));
+
+        if let Some(errors) = errors {
+            let err = errors[i];
+            let whisker_top = y_of(value + err);
+            let whisker_bottom = y_of(value - err);
+            let cap_half = bar_width / 4.0;
+            svg.push_str(&format!(
+                r#"<line x1="{cx:.2}" y1="{whisker_top:.2}" x2="{cx:.2}" y2="{whisker_bottom:.2}" stroke="black"/>"#
+            ));
+            svg.push_str(&format!(
+                r#"<line x1="{:.2}" y1="{whisker_top:.2}" x2="{:.2}" y2="{whisker_top:.2}" stroke="black"/>"#,
+                cx - cap_half,
+                cx + cap_half
+            ));
+            svg.push_str(&format!(
+                r#"<line x1="{:.2}" y1="{whisker_bottom:.2}" x2="{:.2}" y2="{whisker_bottom:.2}" stroke="black"/>"#,
+                cx - cap_half,
+                cx + cap_half
+            ));
+        }
+
+        svg.push_str(&format!(
+            r#"<text x="{cx:.2}" y="{:.2}" font-size="12" text-anchor="middle">{label}</text>"#,
+            config.height - config.margin / 2.0
+        ));
+    }
+
+    svg.push_str("</svg>");
+    Ok(svg)
+}
+
+/// How [`grouped_bar_chart`] arranges multiple series within each label
+/// slot: side by side, or accumulated into one stacked bar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BarMode {
+    Grouped,
+    Stacked,
+}
+
+/// Renders one bar per `(label, series)` combination: `series` is a list of
+/// `(name, values)`, each `values` holding one entry per `labels` entry,
+/// drawn side by side (`BarMode::Grouped`) or stacked vertically
+/// (`BarMode::Stacked`), with a color per series and a legend.
+/// [`VizError::InvalidData`] if any series doesn't have exactly
+/// `labels.len()` values.
+pub fn grouped_bar_chart(labels: &[String], series: &[(String, Vec<f64>)], config: &PlotConfig, mode: BarMode) -> VizResult<String> {
+    for (name, values) in series {
+        if values.len() != labels.len() {
+            return Err(VizError::InvalidData(format!(
+                "series \"{name}\" has {} values but there are {} labels",
+                values.len(),
+                labels.len()
+            )));
+        }
+    }
+
+    let bar_values: Vec<f64> = match mode {
+        BarMode::Grouped => series.iter().flat_map(|(_, vs)| vs.iter().copied()).collect(),
+        BarMode::Stacked => {
+            (0..labels.len()).map(|i| series.iter().map(|(_, vs)| vs[i]).sum::<f64>()).collect()
+        }
+    };
+    let (y_low, y_high) = bar_values
+        .iter()
+        .copied()
+        .chain([0.0])
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), x| (lo.min(x), hi.max(x)));
+    let (y_low, y_high) = if y_low < y_high { (y_low, y_high) } else { (y_low - 0.5, y_high + 0.5) };
+
+    let legend_height = LEGEND_LINE_HEIGHT * series.len() as f64;
+    let plot_width = config.width - 2.0 * config.margin;
+    let plot_height = config.height - 2.0 * config.margin - legend_height;
+    let slot_width = plot_width / labels.len().max(1) as f64;
+    let sy = plot_height / (y_high - y_low);
+    let y_of = |value: f64| config.margin + (plot_height - (value - y_low) * sy);
+
+    let width = config.width;
+    let height = config.height;
+    let mut svg = format!(r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}">"#);
+
+    match mode {
+        BarMode::Grouped => {
+            let group_width = slot_width * BOX_WIDTH_FRACTION;
+            let bar_width = group_width / series.len().max(1) as f64;
+            for (label_idx, label) in labels.iter().enumerate() {
+                let group_x = config.margin + slot_width * (label_idx as f64 + 0.5) - group_width / 2.0;
+                for (series_idx, (_, values)) in series.iter().enumerate() {
+                    let color = PALETTE[series_idx % PALETTE.len()];
+                    let value = values[label_idx];
+                    let bar_x = group_x + bar_width * series_idx as f64;
+                    let bar_top = y_of(value.max(0.0));
+                    let bar_bottom = y_of(value.min(0.0));
+                    svg.push_str(&format!(
+                        r#"<rect x="{bar_x:.2}" y="{bar_top:.2}" width="{bar_width:.2}" height="{:.2}" fill="{color}"/>"#,
+                        bar_bottom - bar_top
+                    ));
+                }
+                svg.push_str(&format!(
+                    r#"<text x="{:.2}" y="{:.2}" font-size="12" text-anchor="middle">{label}</text>"#,
+                    config.margin + slot_width * (label_idx as f64 + 0.5),
+                    config.margin + plot_height + 14.0
+                ));
+            }
+        }
+        BarMode::Stacked => {
+            let bar_width = slot_width * BOX_WIDTH_FRACTION;
+            for (label_idx, label) in labels.iter().enumerate() {
+                let cx = config.margin + slot_width * (label_idx as f64 + 0.5);
+                let bar_x = cx - bar_width / 2.0;
+                let mut cumulative = 0.0;
+                for (series_idx, (_, values)) in series.iter().enumerate() {
+                    let color = PALETTE[series_idx % PALETTE.len()];
+                    let bottom = y_of(cumulative);
+                    cumulative += values[label_idx];
+                    let top = y_of(cumulative);
+                    svg.push_str(&format!(
+                        r#"<rect x="{bar_x:.2}" y="{top:.2}" width="{bar_width:.2}" height="{:.2}" fill="{color}"/>"#,
+                        bottom - top
+                    ));
+                }
+                svg.push_str(&format!(
+                    r#"<text x="{cx:.2}" y="{:.2}" font-size="12" text-anchor="middle">{label}</text>"#,
+                    config.margin + plot_height + 14.0
+                ));
+            }
+        }
+    }
+
+    for (series_idx, (name, _)) in series.iter().enumerate() {
+        let color = PALETTE[series_idx % PALETTE.len()];
+        let y = config.margin + plot_height + LEGEND_LINE_HEIGHT * (series_idx as f64 + 2.0);
+        svg.push_str(&format!(r#"<rect x="{:.2}" y="{:.2}" width="10" height="10" fill="{color}"/>"#, config.margin, y - 10.0));
+        svg.push_str(&format!(r#"<text x="{:.2}" y="{y:.2}" font-size="12">{name}</text>"#, config.margin + 16.0));
+    }
+
+    svg.push_str("</svg>");
+    Ok(svg)
+}
+
+/// Renders `values` at `timestamps` as a connected line, with an optional
+/// shaded `value ± errors[i]` uncertainty band behind it.
+/// [`VizError::InvalidData`] if `timestamps`, `values`, and (when present)
+/// `errors` don't all have matching lengths.
+pub fn time_series(timestamps: &[f64], values: &[f64], errors: Option<&[f64]>, config: &PlotConfig) -> VizResult<String> {
+    if timestamps.len() != values.len() {
+        return Err(VizError::InvalidData(format!(
+            "timestamps has {} entries but values has {}",
+            timestamps.len(),
+            values.len()
+        )));
+    }
+    if let Some(errors) = errors {
+        if errors.len() != values.len() {
+            return Err(VizError::InvalidData(format!(
+                "errors has {} entries but values has {}",
+                errors.len(),
+                values.len()
+            )));
+        }
+    }
+
+    let (x_low, x_high) = timestamps
+        .iter()
+        .copied()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), x| (lo.min(x), hi.max(x)));
+    let (x_low, x_high) = if x_low < x_high { (x_low, x_high) } else { (x_low - 0.5, x_high + 0.5) };
+    let (y_low, y_high) = values
+        .iter()
+        .enumerate()
+        .flat_map(|(i, &v)| {
+            let err = errors.map(|e| e[i]).unwrap_or(0.0);
+            [v - err, v + err]
+        })
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), x| (lo.min(x), hi.max(x)));
+    let (y_low, y_high) = if y_low < y_high { (y_low, y_high) } else { (y_low - 0.5, y_high + 0.5) };
+
+    let plot_width = config.width - 2.0 * config.margin;
+    let plot_height = config.height - 2.0 * config.margin;
+    let sx = plot_width / (x_high - x_low);
+    let sy = plot_height / (y_high - y_low);
+    let x_of = |x: f64| config.margin + (x - x_low) * sx;
+    let y_of = |y: f64| config.margin + (plot_height - (y - y_low) * sy);
+
+    let width = config.width;
+    let height = config.height;
+    let mut svg = format!(r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}">"#);
+
+    if let Some(errors) = errors {
+        let mut band_points: Vec<String> = timestamps
+            .iter()
+            .zip(values.iter())
+            .zip(errors.iter())
+            .map(|((&t, &v), &e)| format!("{:.2},{:.2}", x_of(t), y_of(v + e)))
+            .collect();
+        band_points.extend(
+            timestamps.iter().zip(values.iter()).zip(errors.iter()).rev().map(|((&t, &v), &e)| format!("{:.2},{:.2}", x_of(t), y_of(v - e))),
+        );
+        svg.push_str(&format!(r#"<polygon points="{}" fill="{}" fill-opacity="0.25"/>"#, band_points.join(" "), PALETTE[0]));
+    }
+
+    let line_points: Vec<String> = timestamps.iter().zip(values.iter()).map(|(&t, &v)| format!("{:.2},{:.2}", x_of(t), y_of(v))).collect();
+    svg.push_str(&format!(r#"<polyline points="{}" fill="none" stroke="{}"/>"#, line_points.join(" "), PALETTE[0]));
+
+    svg.push_str("</svg>");
+    Ok(svg)
+}
+
+const COLORBAR_WIDTH: f64 = 20.0;
+const COLORBAR_GAP: f64 = 10.0;
+const COLORBAR_STEPS: usize = 32;
+
+/// Renders `data` (a rectangular matrix, rows top to bottom) as a grid of
+/// colored cells through `config.color_map`, with a colorbar strip showing
+/// the value-to-color scale and its min/max. [`VizError::InvalidData`] if
+/// `data` is empty or its rows don't all have the same length.
+pub fn heatmap(data: &[Vec<f64>], config: &PlotConfig) -> VizResult<String> {
+    if data.is_empty() || data[0].is_empty() {
+        return Err(VizError::InvalidData("heatmap data must be a non-empty matrix".to_string()));
+    }
+    let cols = data[0].len();
+    if data.iter().any(|row| row.len() != cols) {
+        return Err(VizError::InvalidData("heatmap rows must all have the same length".to_string()));
+    }
+
+    let (min_val, max_val) = data
+        .iter()
+        .flatten()
+        .copied()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| (lo.min(v), hi.max(v)));
+    let span = if max_val > min_val { max_val - min_val } else { 1.0 };
+
+    let plot_width = config.width - 2.0 * config.margin - COLORBAR_WIDTH - COLORBAR_GAP;
+    let plot_height = config.height - 2.0 * config.margin;
+    let rows = data.len();
+    let cell_width = plot_width / cols as f64;
+    let cell_height = plot_height / rows as f64;
+
+    let width = config.width;
+    let height = config.height;
+    let mut svg = format!(r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}">"#);
+
+    for (r, row) in data.iter().enumerate() {
+        for (c, &value) in row.iter().enumerate() {
+            let (red, green, blue) = config.color_map.color((value - min_val) / span);
+            let x = config.margin + c as f64 * cell_width;
+            let y = config.margin + r as f64 * cell_height;
+            svg.push_str(&format!(
+                r#"<rect x="{x:.2}" y="{y:.2}" width="{cell_width:.2}" height="{cell_height:.2}" fill="rgb({red},{green},{blue})"/>"#
+            ));
+        }
+    }
+
+    let bar_x = config.margin + plot_width + COLORBAR_GAP;
+    let step_height = plot_height / COLORBAR_STEPS as f64;
+    for i in 0..COLORBAR_STEPS {
+        // The bar reads top-to-bottom as max-to-min, so step 0 (the top) is t = 1.
+        let t = 1.0 - i as f64 / (COLORBAR_STEPS - 1) as f64;
+        let (red, green, blue) = config.color_map.color(t);
+        let y = config.margin + i as f64 * step_height;
+        svg.push_str(&format!(
+            r#"<rect x="{bar_x:.2}" y="{y:.2}" width="{COLORBAR_WIDTH:.2}" height="{:.2}" fill="rgb({red},{green},{blue})"/>"#,
+            step_height + 0.5
+        ));
+    }
+    svg.push_str(&format!(r#"<text x="{bar_x:.2}" y="{:.2}" font-size="10">{max_val:.2}</text>"#, config.margin - 2.0));
+    svg.push_str(&format!(
+        r#"<text x="{bar_x:.2}" y="{:.2}" font-size="10">{min_val:.2}</text>"#,
+        config.margin + plot_height + 10.0
+    ));
+
+    svg.push_str("</svg>");
+    Ok(svg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn histogram_counts_every_sample_exactly_once() {
+        let samples = [0.0, 1.0, 2.0, 3.0, 4.0, 5.0];
+        let hist = histogram(&samples, 3);
+        let total: f64 = hist.bars.iter().map(|b| b.height).sum();
+        assert_eq!(total, samples.len() as f64);
+    }
+
+    #[test]
+    fn histogram_of_a_single_unique_value_does_not_divide_by_zero() {
+        let samples = [5.0; 10];
+        let hist = histogram(&samples, 10);
+        assert_eq!(hist.bin_width, 0.0);
+        assert_eq!(hist.bars, vec![Bar { center: 5.0, height: 10.0 }]);
+    }
+
+    #[test]
+    fn histogram_with_data_counts_sum_to_the_input_length() {
+        let samples = [0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0];
+        let (svg, triples) = histogram_with_data(&samples, 3, &PlotConfig::default()).unwrap();
+        let total: u32 = triples.iter().map(|&(_, _, count)| count).sum();
+        assert_eq!(total, samples.len() as u32);
+        assert!(svg.starts_with("<svg"));
+    }
+
+    #[test]
+    fn distribution_plot_bars_integrate_to_about_one() {
+        let samples: Vec<f64> = (0..10_000).map(|i| (i % 100) as f64 / 100.0).collect();
+        let plot = distribution_plot(&samples, 20, false);
+        let integral: f64 = plot.bars.iter().map(|b| b.height * plot.bin_width).sum();
+        assert!((integral - 1.0).abs() < 1e-9, "integral was {integral}");
+    }
+
+    #[test]
+    fn distribution_plot_y_label_is_density() {
+        let plot = distribution_plot(&[1.0, 2.0, 3.0], 2, false);
+        assert_eq!(plot.y_label, "Density");
+    }
+
+    #[test]
+    fn distribution_plot_of_a_single_unique_value_does_not_panic() {
+        let plot = distribution_plot(&[7.0; 5], 10, false);
+        assert_eq!(plot.bars, vec![Bar { center: 7.0, height: 1.0 }]);
+    }
+
+    #[test]
+    fn show_fit_overlays_a_normal_curve_with_enough_samples() {
+        let samples: Vec<f64> = (0..1000).map(|i| (i as f64 - 500.0) / 100.0).collect();
+        let plot = distribution_plot(&samples, 20, true);
+        assert!(plot.fit.is_some());
+        assert_eq!(plot.fit.unwrap().len(), 200);
+    }
+
+    #[test]
+    fn show_fit_is_none_without_samples() {
+        let plot = distribution_plot(&[], 10, true);
+        assert!(plot.fit.is_none());
+    }
+
+    #[test]
+    fn multi_distribution_plot_legend_lists_every_series_name() {
+        let a: Vec<f64> = (0..100).map(|i| i as f64 / 10.0).collect();
+        let b: Vec<f64> = (0..100).map(|i| 50.0 + i as f64 / 10.0).collect();
+        let svg = multi_distribution_plot(&[("series a".to_string(), a), ("series b".to_string(), b)], 10);
+        assert!(svg.contains(">series a<"), "missing legend label for series a:\n{svg}");
+        assert!(svg.contains(">series b<"), "missing legend label for series b:\n{svg}");
+    }
+
+    #[test]
+    fn multi_distribution_plot_spans_the_global_min_and_max() {
+        let a = vec![0.0, 1.0];
+        let b = vec![100.0, 101.0];
+        let svg = multi_distribution_plot(&[("a".to_string(), a), ("b".to_string(), b)], 4);
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with("</svg>"));
+    }
+
+    #[test]
+    fn box_stats_of_an_empty_slice_is_none() {
+        assert_eq!(box_stats(&[]), None);
+    }
+
+    #[test]
+    fn box_stats_quartiles_match_a_known_data_set() {
+        let samples: Vec<f64> = (1..=9).map(|i| i as f64).collect();
+        let stats = box_stats(&samples).unwrap();
+        assert_eq!(stats.median, 5.0);
+        assert_eq!(stats.q1, 3.0);
+        assert_eq!(stats.q3, 7.0);
+        assert!(stats.outliers.is_empty());
+    }
+
+    #[test]
+    fn box_stats_matches_hand_computed_interpolated_quartiles() {
+        let samples: Vec<f64> = (1..=10).map(|i| i as f64).collect();
+        let stats = BoxStats::from_data(&samples).unwrap();
+        assert_eq!(stats.q1, 3.25);
+        assert_eq!(stats.q3, 7.75);
+    }
+
+    #[test]
+    fn box_stats_flags_a_far_outlier() {
+        let mut samples: Vec<f64> = (1..=9).map(|i| i as f64).collect();
+        samples.push(1000.0);
+        let stats = box_stats(&samples).unwrap();
+        assert_eq!(stats.outliers, vec![1000.0]);
+        assert!(stats.max < 1000.0);
+    }
+
+    #[test]
+    fn box_plot_rejects_an_empty_group() {
+        let groups = vec![("empty".to_string(), vec![])];
+        let err = box_plot(&groups, &PlotConfig::default()).unwrap_err();
+        assert_eq!(err, VizError::EmptyGroup("empty".to_string()));
+    }
+
+    #[test]
+    fn bar_chart_rejects_mismatched_error_lengths() {
+        let labels = vec!["a".to_string(), "b".to_string()];
+        let values = vec![1.0, 2.0];
+        let errors = vec![0.1];
+        let err = bar_chart(&labels, &values, Some(&errors), &PlotConfig::default()).unwrap_err();
+        assert!(matches!(err, VizError::InvalidData(_)));
+    }
+
+    #[test]
+    fn bar_chart_with_errors_draws_more_svg_lines_than_without() {
+        let labels = vec!["a".to_string(), "b".to_string()];
+        let values = vec![1.0, 2.0];
+        let errors = vec![0.2, 0.3];
+        let config = PlotConfig::default();
+
+        let without = bar_chart(&labels, &values, None, &config).unwrap();
+        let with = bar_chart(&labels, &values, Some(&errors), &config).unwrap();
+        assert!(with.matches("<line").count() > without.matches("<line").count());
+    }
+
+    #[test]
+    fn time_series_rejects_mismatched_lengths() {
+        let err = time_series(&[0.0, 1.0], &[1.0], None, &PlotConfig::default()).unwrap_err();
+        assert!(matches!(err, VizError::InvalidData(_)));
+    }
+
+    #[test]
+    fn time_series_with_errors_adds_a_shaded_band() {
+        let timestamps = [0.0, 1.0, 2.0];
+        let values = [1.0, 2.0, 1.5];
+        let errors = [0.1, 0.2, 0.1];
+        let config = PlotConfig::default();
+
+        let without = time_series(&timestamps, &values, None, &config).unwrap();
+        let with = time_series(&timestamps, &values, Some(&errors), &config).unwrap();
+        assert!(!without.contains("<polygon"));
+        assert!(with.contains("<polygon"));
+    }
+
+    #[test]
+    fn box_plot_renders_one_labeled_box_per_group() {
+        let groups = vec![
+            ("a".to_string(), (1..=9).map(|i| i as f64).collect()),
+            ("b".to_string(), (10..=20).map(|i| i as f64).collect()),
+        ];
+        let svg = box_plot(&groups, &PlotConfig::default()).unwrap();
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains(">a<"));
+        assert!(svg.contains(">b<"));
+    }
+
+    #[test]
+    fn grouped_bar_chart_rejects_a_series_with_the_wrong_number_of_values() {
+        let labels = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let series = vec![("s1".to_string(), vec![1.0, 2.0])];
+        let err = grouped_bar_chart(&labels, &series, &PlotConfig::default(), BarMode::Grouped).unwrap_err();
+        assert!(matches!(err, VizError::InvalidData(_)));
+    }
+
+    #[test]
+    fn grouped_bar_chart_draws_one_rect_per_series_per_label() {
+        let labels = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let series = vec![("s1".to_string(), vec![1.0, 2.0, 3.0]), ("s2".to_string(), vec![4.0, 5.0, 6.0])];
+        let svg = grouped_bar_chart(&labels, &series, &PlotConfig::default(), BarMode::Grouped).unwrap();
+        assert_eq!(svg.matches("<rect").count(), 6 + series.len());
+        assert!(svg.contains(">s1<") && svg.contains(">s2<"));
+    }
+
+    #[test]
+    fn stacked_bar_chart_draws_one_rect_per_series_per_label() {
+        let labels = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let series = vec![("s1".to_string(), vec![1.0, 2.0, 3.0]), ("s2".to_string(), vec![4.0, 5.0, 6.0])];
+        let svg = grouped_bar_chart(&labels, &series, &PlotConfig::default(), BarMode::Stacked).unwrap();
+        assert_eq!(svg.matches("<rect").count(), 6 + series.len());
+        assert!(svg.contains(">s1<") && svg.contains(">s2<"));
+    }
+
+    #[test]
+    fn heatmap_of_a_3x3_matrix_with_viridis_renders_a_cell_per_entry_and_a_colorbar() {
+        let data = vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0, 6.0], vec![7.0, 8.0, 9.0]];
+        let config = PlotConfig { color_map: ColorMap::Viridis, ..PlotConfig::default() };
+        let svg = heatmap(&data, &config).unwrap();
+        assert_eq!(svg.matches("<rect").count(), 9 + COLORBAR_STEPS);
+        assert!(svg.contains(">9.00<") && svg.contains(">1.00<"));
+    }
+
+    #[test]
+    fn heatmap_rejects_ragged_rows() {
+        let data = vec![vec![1.0, 2.0], vec![3.0]];
+        let err = heatmap(&data, &PlotConfig::default()).unwrap_err();
+        assert!(matches!(err, VizError::InvalidData(_)));
+    }
+
+    #[test]
+    fn heatmap_of_a_constant_matrix_does_not_produce_nan_colors() {
+        let data = vec![vec![5.0, 5.0], vec![5.0, 5.0]];
+        let svg = heatmap(&data, &PlotConfig::default()).unwrap();
+        assert!(!svg.contains("NaN"), "degenerate heatmap produced NaN colors:\n{svg}");
+    }
+}
@@ -0,0 +1,307 @@
+//! Exposes the SVG-producing plot builders as betlang natives, so a program
+//! can render a chart without leaving the language. Argument-shape mismatches
+//! (wrong value type, wrong tuple arity) are native-call failures, same as
+//! every other native module; failures the plot builders themselves report
+//! (an empty group, ragged data) come back as [`Value::Error`] instead, since
+//! they're about the data rather than the call.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use bet_rt::value::{NativeFunction, Value};
+
+use crate::{bar_chart, box_plot, heatmap, histogram_with_data, PlotConfig, VizError};
+use crate::chart::{line_plot, scatter_plot, write_with_parents};
+
+fn as_float(v: &Value, what: &str) -> Result<f64, String> {
+    match v {
+        Value::Float(x) => Ok(*x),
+        Value::Int(i) => Ok(*i as f64),
+        other => Err(format!("expected {what} to be numeric, found {other}")),
+    }
+}
+
+fn as_string(v: &Value, what: &str) -> Result<String, String> {
+    match v {
+        Value::String(s) => Ok(s.clone()),
+        other => Err(format!("expected {what} to be a String, found {other}")),
+    }
+}
+
+fn as_floats(v: &Value) -> Result<Vec<f64>, String> {
+    match v {
+        Value::List(items) => items.iter().map(|x| as_float(x, "a list element")).collect(),
+        other => Err(format!("expected a list of numbers, found {other}")),
+    }
+}
+
+/// A list of `(x, y)` tuples, as e.g. `line_plot`/`scatter_plot` take.
+fn as_points(v: &Value) -> Result<Vec<(f64, f64)>, String> {
+    match v {
+        Value::List(items) => items
+            .iter()
+            .map(|item| match item {
+                Value::Tuple(pair) if pair.len() == 2 => Ok((as_float(&pair[0], "a point's x")?, as_float(&pair[1], "a point's y")?)),
+                other => Err(format!("expected an (x, y) tuple, found {other}")),
+            })
+            .collect(),
+        other => Err(format!("expected a list of (x, y) tuples, found {other}")),
+    }
+}
+
+/// A list of `(label, value)` tuples, as `bar_chart` takes.
+fn as_label_value_pairs(v: &Value) -> Result<(Vec<String>, Vec<f64>), String> {
+    match v {
+        Value::List(items) => items
+            .iter()
+            .map(|item| match item {
+                Value::Tuple(pair) if pair.len() == 2 => Ok((as_string(&pair[0], "a bar's label")?, as_float(&pair[1], "a bar's value")?)),
+                other => Err(format!("expected a (label, value) tuple, found {other}")),
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(|pairs| pairs.into_iter().unzip()),
+        other => Err(format!("expected a list of (label, value) tuples, found {other}")),
+    }
+}
+
+/// A list of `(name, samples)` tuples, as `box_plot` takes.
+fn as_named_groups(v: &Value) -> Result<Vec<(String, Vec<f64>)>, String> {
+    match v {
+        Value::List(items) => items
+            .iter()
+            .map(|item| match item {
+                Value::Tuple(pair) if pair.len() == 2 => Ok((as_string(&pair[0], "a group's name")?, as_floats(&pair[1])?)),
+                other => Err(format!("expected a (name, samples) tuple, found {other}")),
+            })
+            .collect(),
+        other => Err(format!("expected a list of (name, samples) tuples, found {other}")),
+    }
+}
+
+/// A list of rows, each a list of numbers, as `heatmap` takes.
+fn as_matrix(v: &Value) -> Result<Vec<Vec<f64>>, String> {
+    match v {
+        Value::List(rows) => rows.iter().map(as_floats).collect(),
+        other => Err(format!("expected a list of lists, found {other}")),
+    }
+}
+
+fn viz_result(result: Result<String, VizError>) -> Value {
+    match result {
+        Ok(svg) => Value::String(svg),
+        Err(e) => Value::Error(e.to_string()),
+    }
+}
+
+fn native(name: &str, arity: usize, func: impl Fn(&[Value]) -> Result<Value, String> + Send + Sync + 'static) -> (String, NativeFunction) {
+    (
+        name.to_string(),
+        NativeFunction {
+            name: name.to_string(),
+            arity,
+            func: Arc::new(func),
+        },
+    )
+}
+
+pub fn native_functions() -> HashMap<String, NativeFunction> {
+    let mut m = HashMap::new();
+
+    let (name, f) = native("histogram", 2, |args| {
+        let samples = as_floats(&args[0])?;
+        let bins = match &args[1] {
+            Value::Int(i) if *i >= 0 => *i as usize,
+            other => return Err(format!("expected a non-negative bin count, found {other}")),
+        };
+        match histogram_with_data(&samples, bins, &PlotConfig::default()) {
+            Ok((svg, triples)) => {
+                let bins = triples
+                    .into_iter()
+                    .map(|(start, end, count)| Value::Tuple(vec![Value::Float(start), Value::Float(end), Value::Int(count as i64)]))
+                    .collect();
+                Ok(Value::Tuple(vec![Value::String(svg), Value::List(bins)]))
+            }
+            Err(e) => Ok(Value::Error(e.to_string())),
+        }
+    });
+    m.insert(name, f);
+
+    let (name, f) = native("line_plot", 1, |args| {
+        let points = as_points(&args[0])?;
+        Ok(viz_result(line_plot(&points, &PlotConfig::default())))
+    });
+    m.insert(name, f);
+
+    let (name, f) = native("scatter_plot", 1, |args| {
+        let points = as_points(&args[0])?;
+        Ok(viz_result(scatter_plot(&points, &PlotConfig::default())))
+    });
+    m.insert(name, f);
+
+    let (name, f) = native("bar_chart", 1, |args| {
+        let (labels, values) = as_label_value_pairs(&args[0])?;
+        Ok(viz_result(bar_chart(&labels, &values, None, &PlotConfig::default())))
+    });
+    m.insert(name, f);
+
+    let (name, f) = native("box_plot", 1, |args| {
+        let groups = as_named_groups(&args[0])?;
+        Ok(viz_result(box_plot(&groups, &PlotConfig::default())))
+    });
+    m.insert(name, f);
+
+    let (name, f) = native("heatmap", 1, |args| {
+        let matrix = as_matrix(&args[0])?;
+        Ok(viz_result(heatmap(&matrix, &PlotConfig::default())))
+    });
+    m.insert(name, f);
+
+    let (name, f) = native("save_plot", 2, |args| {
+        let bytes = match &args[0] {
+            Value::String(svg) => svg.as_bytes().to_vec(),
+            Value::Bytes(bytes) => bytes.clone(),
+            other => return Err(format!("expected an SVG String or PNG Bytes, found {other}")),
+        };
+        let path = as_string(&args[1], "a path")?;
+        Ok(match write_with_parents(&path, &bytes) {
+            Ok(()) => Value::Unit,
+            Err(e) => Value::Error(e.to_string()),
+        })
+    });
+    m.insert(name, f);
+
+    m
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(x: f64, y: f64) -> Value {
+        Value::Tuple(vec![Value::Float(x), Value::Float(y)])
+    }
+
+    #[test]
+    fn line_plot_native_renders_an_svg_for_valid_points() {
+        let funcs = native_functions();
+        let points = Value::List(im::vector![point(0.0, 0.0), point(1.0, 1.0)]);
+        let result = (funcs["line_plot"].func)(&[points]).unwrap();
+        assert!(matches!(result, Value::String(svg) if svg.starts_with("<svg")));
+    }
+
+    #[test]
+    fn line_plot_native_rejects_a_non_list_argument() {
+        let funcs = native_functions();
+        assert!((funcs["line_plot"].func)(&[Value::Int(5)]).is_err());
+    }
+
+    #[test]
+    fn scatter_plot_native_renders_an_svg_for_valid_points() {
+        let funcs = native_functions();
+        let points = Value::List(im::vector![point(0.0, 0.0), point(1.0, 2.0)]);
+        let result = (funcs["scatter_plot"].func)(&[points]).unwrap();
+        assert!(matches!(result, Value::String(svg) if svg.starts_with("<svg")));
+    }
+
+    #[test]
+    fn bar_chart_native_renders_an_svg_for_label_value_pairs() {
+        let funcs = native_functions();
+        let bars = Value::List(im::vector![
+            Value::Tuple(vec![Value::String("a".to_string()), Value::Float(1.0)]),
+            Value::Tuple(vec![Value::String("b".to_string()), Value::Float(2.0)]),
+        ]);
+        let result = (funcs["bar_chart"].func)(&[bars]).unwrap();
+        assert!(matches!(result, Value::String(svg) if svg.starts_with("<svg")));
+    }
+
+    #[test]
+    fn bar_chart_native_rejects_a_tuple_missing_a_label() {
+        let funcs = native_functions();
+        let bars = Value::List(im::vector![Value::Tuple(vec![Value::Int(1), Value::Float(1.0)])]);
+        assert!((funcs["bar_chart"].func)(&[bars]).is_err());
+    }
+
+    #[test]
+    fn box_plot_native_returns_a_value_error_for_an_empty_group() {
+        let funcs = native_functions();
+        let groups = Value::List(im::vector![Value::Tuple(vec![
+            Value::String("empty".to_string()),
+            Value::List(im::Vector::new()),
+        ])]);
+        let result = (funcs["box_plot"].func)(&[groups]).unwrap();
+        assert!(matches!(result, Value::Error(_)));
+    }
+
+    #[test]
+    fn box_plot_native_renders_an_svg_for_valid_groups() {
+        let funcs = native_functions();
+        let groups = Value::List(im::vector![Value::Tuple(vec![
+            Value::String("a".to_string()),
+            Value::List(im::vector![Value::Int(1), Value::Int(2), Value::Int(3)]),
+        ])]);
+        let result = (funcs["box_plot"].func)(&[groups]).unwrap();
+        assert!(matches!(result, Value::String(svg) if svg.starts_with("<svg")));
+    }
+
+    #[test]
+    fn heatmap_native_renders_an_svg_for_a_rectangular_matrix() {
+        let funcs = native_functions();
+        let matrix = Value::List(im::vector![
+            Value::List(im::vector![Value::Float(1.0), Value::Float(2.0)]),
+            Value::List(im::vector![Value::Float(3.0), Value::Float(4.0)]),
+        ]);
+        let result = (funcs["heatmap"].func)(&[matrix]).unwrap();
+        assert!(matches!(result, Value::String(svg) if svg.starts_with("<svg")));
+    }
+
+    #[test]
+    fn heatmap_native_returns_a_value_error_for_ragged_rows() {
+        let funcs = native_functions();
+        let matrix = Value::List(im::vector![
+            Value::List(im::vector![Value::Float(1.0), Value::Float(2.0)]),
+            Value::List(im::vector![Value::Float(3.0)]),
+        ]);
+        let result = (funcs["heatmap"].func)(&[matrix]).unwrap();
+        assert!(matches!(result, Value::Error(_)));
+    }
+
+    #[test]
+    fn save_plot_native_writes_a_histogram_svg_to_a_file() {
+        let funcs = native_functions();
+        let samples = Value::List(im::vector![Value::Int(1), Value::Int(2), Value::Int(3), Value::Int(4)]);
+        let histogram = (funcs["histogram"].func)(&[samples, Value::Int(2)]).unwrap();
+        let svg = match &histogram {
+            Value::Tuple(parts) => parts[0].clone(),
+            other => panic!("expected a (svg, bins) tuple, found {other:?}"),
+        };
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("plots/out.svg");
+        let result = (funcs["save_plot"].func)(&[svg, Value::String(path.to_string_lossy().to_string())]).unwrap();
+
+        assert_eq!(result, Value::Unit);
+        let written = std::fs::read(&path).unwrap();
+        assert!(!written.is_empty());
+    }
+
+    #[test]
+    fn save_plot_native_rejects_a_non_writable_argument() {
+        let funcs = native_functions();
+        let result = (funcs["save_plot"].func)(&[Value::Int(1), Value::String("out.svg".to_string())]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn histogram_native_returns_svg_and_bin_triples() {
+        let funcs = native_functions();
+        let samples = Value::List(im::vector![Value::Int(1), Value::Int(2), Value::Int(3), Value::Int(4)]);
+        let result = (funcs["histogram"].func)(&[samples, Value::Int(2)]).unwrap();
+        match result {
+            Value::Tuple(parts) if parts.len() == 2 => {
+                assert!(matches!(&parts[0], Value::String(svg) if svg.starts_with("<svg")));
+                assert!(matches!(&parts[1], Value::List(_)));
+            }
+            other => panic!("expected a (svg, bins) tuple, found {other:?}"),
+        }
+    }
+}
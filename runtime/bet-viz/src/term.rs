@@ -0,0 +1,464 @@
+//! Terminal (braille-canvas) plots for quick inspection in a shell, via
+//! [`textplots`]. Complements the SVG builders at the crate root, which are
+//! meant for embedding in reports rather than reading in a terminal.
+
+use rgb::RGB8;
+use textplots::{Chart, ColorPlot, Plot, Shape};
+
+use crate::{box_stats, VizError, VizResult};
+
+/// Terminal-friendly RGB equivalents of [`crate::PALETTE`], cycled across
+/// series in [`term_multi_line_plot`].
+const TERM_PALETTE: [RGB8; 6] = [
+    RGB8 { r: 31, g: 119, b: 180 },
+    RGB8 { r: 255, g: 127, b: 14 },
+    RGB8 { r: 44, g: 160, b: 44 },
+    RGB8 { r: 214, g: 39, b: 40 },
+    RGB8 { r: 148, g: 103, b: 189 },
+    RGB8 { r: 140, g: 86, b: 75 },
+];
+
+/// Layout for [`term_line_plot`]/[`term_box_plot`]/[`term_heatmap`].
+/// `width`/`height` of `0` auto-detect the current terminal's size via
+/// [`terminal_size`] and fall back to `80x20` when that fails (e.g. output
+/// isn't a TTY).
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct TermPlotConfig {
+    pub width: u32,
+    pub height: u32,
+    /// 24-bit color to wrap the rendered chart in, as a `\x1b[38;2;r;g;bm`
+    /// escape sequence. Ignored (as if `None`) when the `NO_COLOR`
+    /// environment variable is set, per <https://no-color.org>.
+    pub ansi_color: Option<(u8, u8, u8)>,
+}
+
+impl TermPlotConfig {
+    /// Forces no ANSI color, for piped output that shouldn't carry escape
+    /// sequences regardless of `ansi_color`.
+    pub fn no_color(mut self) -> Self {
+        self.ansi_color = None;
+        self
+    }
+}
+
+/// Wraps `text` in `config.ansi_color`'s escape sequence, unless it's unset
+/// or the `NO_COLOR` environment variable is present.
+fn colorize(text: &str, config: &TermPlotConfig) -> String {
+    match config.ansi_color {
+        Some((r, g, b)) if std::env::var_os("NO_COLOR").is_none() => format!("\x1b[38;2;{r};{g};{b}m{text}\x1b[0m"),
+        _ => text.to_string(),
+    }
+}
+
+const DEFAULT_WIDTH: u32 = 80;
+const DEFAULT_HEIGHT: u32 = 20;
+
+/// Resolves `config`'s width/height, auto-detecting the terminal size for
+/// any dimension left at `0`.
+fn resolve_dimensions(config: &TermPlotConfig) -> (u32, u32) {
+    let detected = terminal_size::terminal_size();
+    let width = if config.width == 0 {
+        detected.map(|(w, _)| w.0 as u32).unwrap_or(DEFAULT_WIDTH)
+    } else {
+        config.width
+    };
+    let height = if config.height == 0 {
+        detected.map(|(_, h)| h.0 as u32).unwrap_or(DEFAULT_HEIGHT)
+    } else {
+        config.height
+    };
+    (width, height)
+}
+
+/// Renders `points` as a braille-canvas line plot sized per `config`. Points
+/// are sorted by x first, so unsorted or descending input still draws a
+/// sensible left-to-right line instead of a zigzag.
+pub fn term_line_plot(points: &[(f32, f32)], config: &TermPlotConfig) -> String {
+    let (width, height) = resolve_dimensions(config);
+    let (xmin, xmax) = points
+        .iter()
+        .map(|&(x, _)| x)
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), x| (lo.min(x), hi.max(x)));
+    let (xmin, xmax) = if xmin < xmax { (xmin, xmax) } else { (xmin - 1.0, xmax + 1.0) };
+
+    let mut sorted = points.to_vec();
+    sorted.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+    let rendered = Chart::new(width, height, xmin, xmax).lineplot(&Shape::Lines(&sorted)).to_string();
+    colorize(&rendered, config)
+}
+
+/// Renders one `(name, points)` series per entry of `series` as a braille-canvas
+/// line plot, each cycled through [`TERM_PALETTE`] and sharing one x/y range,
+/// followed by one legend line giving each series' name in its plotted color.
+/// Points within each series are sorted by x first, like [`term_line_plot`].
+/// `config.ansi_color` is ignored; series colors come from the palette
+/// instead. [`VizError::InvalidData`] if `series` is empty.
+pub fn term_multi_line_plot(series: &[(String, Vec<(f32, f32)>)], config: &TermPlotConfig) -> VizResult<String> {
+    if series.is_empty() {
+        return Err(VizError::InvalidData("multi-series line plot needs at least one series".to_string()));
+    }
+
+    let (width, height) = resolve_dimensions(config);
+    let all_points: Vec<(f32, f32)> = series.iter().flat_map(|(_, points)| points.iter().copied()).collect();
+    let (xmin, xmax) = all_points
+        .iter()
+        .map(|&(x, _)| x)
+        .fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), x| (lo.min(x), hi.max(x)));
+    let (xmin, xmax) = if xmin < xmax { (xmin, xmax) } else { (xmin - 1.0, xmax + 1.0) };
+
+    let names: Vec<&str> = series.iter().map(|(name, _)| name.as_str()).collect();
+    let colors: Vec<RGB8> = (0..series.len()).map(|i| TERM_PALETTE[i % TERM_PALETTE.len()]).collect();
+    let sorted_points: Vec<Vec<(f32, f32)>> = series
+        .iter()
+        .map(|(_, points)| {
+            let mut sorted = points.clone();
+            sorted.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+            sorted
+        })
+        .collect();
+
+    let shapes: Vec<Shape> = sorted_points.iter().map(|points| Shape::Lines(points)).collect();
+    let mut chart = Chart::new(width, height, xmin, xmax);
+    let mut chart_ref = &mut chart;
+    for (shape, color) in shapes.iter().zip(&colors) {
+        chart_ref = chart_ref.linecolorplot(shape, *color);
+    }
+    let rendered = chart_ref.to_string();
+
+    let legend = names
+        .iter()
+        .zip(&colors)
+        .map(|(name, color)| colorize(name, &TermPlotConfig { ansi_color: Some((color.r, color.g, color.b)), ..*config }))
+        .collect::<Vec<_>>()
+        .join("  ");
+
+    Ok(format!("{rendered}\n{legend}"))
+}
+
+/// Renders one ASCII whisker diagram per `(name, samples)` group, one per
+/// line, scaled to `config`'s width (auto-detected like [`term_line_plot`]
+/// when `0`). Each line looks like `name  ├──[══|══]──┤`, with `[`/`]`
+/// marking the box's q1/q3, `|` the median, and `├`/`┤` the whisker ends.
+/// [`VizError::EmptyGroup`] if any group has no samples.
+pub fn term_box_plot(groups: &[(String, Vec<f64>)], config: &TermPlotConfig) -> VizResult<String> {
+    let stats = groups
+        .iter()
+        .map(|(name, samples)| {
+            box_stats(samples).map(|s| (name.as_str(), s)).ok_or_else(|| VizError::EmptyGroup(name.clone()))
+        })
+        .collect::<VizResult<Vec<_>>>()?;
+
+    let (width, _) = resolve_dimensions(config);
+    let label_width = stats.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+    let chart_width = (width as usize).saturating_sub(label_width + 2).max(10);
+
+    let (global_min, global_max) = stats
+        .iter()
+        .flat_map(|(_, s)| s.outliers.iter().copied().chain([s.min, s.max]))
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), x| (lo.min(x), hi.max(x)));
+    let (global_min, global_max) = if global_min < global_max { (global_min, global_max) } else { (global_min - 0.5, global_max + 0.5) };
+
+    let col_of = |value: f64| -> usize {
+        (((value - global_min) / (global_max - global_min)) * (chart_width - 1) as f64).round() as usize
+    };
+
+    let mut lines = Vec::with_capacity(stats.len());
+    for (name, s) in &stats {
+        let mut row = vec![' '; chart_width];
+        let (min_c, q1_c, med_c, q3_c, max_c) = (col_of(s.min), col_of(s.q1), col_of(s.median), col_of(s.q3), col_of(s.max));
+        for c in row.iter_mut().take(max_c + 1).skip(min_c) {
+            *c = '─';
+        }
+        for c in row.iter_mut().take(q3_c + 1).skip(q1_c) {
+            *c = '═';
+        }
+        row[min_c] = '├';
+        row[max_c] = '┤';
+        row[q1_c] = '[';
+        row[q3_c] = ']';
+        row[med_c] = '|';
+        lines.push(format!("{name:label_width$}  {}", row.into_iter().collect::<String>()));
+    }
+    Ok(colorize(&lines.join("\n"), config))
+}
+
+/// Shades, lightest to darkest, the block characters [`term_heatmap`] maps
+/// normalized values through.
+const SHADE_CHARS: [char; 5] = [' ', '░', '▒', '▓', '█'];
+
+/// Renders `data` (a rectangular matrix, rows top to bottom) as block-shaded
+/// text, one row per line, resampled to at most `config`'s width columns
+/// (auto-detected like [`term_line_plot`] when `0`). [`VizError::InvalidData`]
+/// if `data` is empty or its rows don't all have the same length.
+pub fn term_heatmap(data: &[Vec<f64>], config: &TermPlotConfig) -> VizResult<String> {
+    if data.is_empty() || data[0].is_empty() {
+        return Err(VizError::InvalidData("heatmap data must be a non-empty matrix".to_string()));
+    }
+    let cols = data[0].len();
+    if data.iter().any(|row| row.len() != cols) {
+        return Err(VizError::InvalidData("heatmap rows must all have the same length".to_string()));
+    }
+
+    let (min_val, max_val) = data
+        .iter()
+        .flatten()
+        .copied()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), v| (lo.min(v), hi.max(v)));
+    let span = if max_val > min_val { max_val - min_val } else { 1.0 };
+
+    let (width, _) = resolve_dimensions(config);
+    let target_cols = (width as usize).min(cols).max(1);
+
+    let lines: Vec<String> = data
+        .iter()
+        .map(|row| {
+            (0..target_cols)
+                .map(|tc| {
+                    let src_idx = tc * cols / target_cols;
+                    let t = ((row[src_idx] - min_val) / span).clamp(0.0, 1.0);
+                    SHADE_CHARS[(t * (SHADE_CHARS.len() - 1) as f64).round() as usize]
+                })
+                .collect()
+        })
+        .collect();
+    Ok(colorize(&lines.join("\n"), config))
+}
+
+/// Renders `data` bucketed into `bins` bins (via [`crate::histogram`]) as
+/// one block-bar row per bin, labeled with the bin's center and scaled to
+/// `config`'s width (auto-detected like [`term_line_plot`] when `0`).
+/// [`VizError::InvalidData`] if `data` is empty.
+pub fn term_histogram(data: &[f64], bins: usize, config: &TermPlotConfig) -> VizResult<String> {
+    if data.is_empty() {
+        return Err(VizError::InvalidData("histogram data must not be empty".to_string()));
+    }
+    let hist = crate::histogram(data, bins);
+    let labels: Vec<String> = hist.bars.iter().map(|b| format!("{:.2}", b.center)).collect();
+    let label_width = labels.iter().map(|l| l.len()).max().unwrap_or(0);
+
+    let (width, _) = resolve_dimensions(config);
+    let bar_width = (width as usize).saturating_sub(label_width + 2).max(10);
+    let max_count = hist.bars.iter().map(|b| b.height).fold(0.0_f64, f64::max).max(1.0);
+
+    let lines: Vec<String> = hist
+        .bars
+        .iter()
+        .zip(&labels)
+        .map(|(bar, label)| {
+            let filled = ((bar.height / max_count) * bar_width as f64).round() as usize;
+            let bar_str: String = std::iter::repeat_n('█', filled).collect();
+            format!("{label:>label_width$}  {bar_str} {}", bar.height as u64)
+        })
+        .collect();
+    Ok(colorize(&lines.join("\n"), config))
+}
+
+/// Like [`term_histogram`], but picks the bin count automatically via the
+/// Freedman-Diaconis rule (bin width `2 * IQR * n^(-1/3)`), falling back to
+/// Sturges' rule (`log2(n) + 1` bins) when the interquartile range is zero
+/// (e.g. highly repetitive data) and a bin width can't be derived from it.
+pub fn term_histogram_auto(data: &[f64], config: &TermPlotConfig) -> VizResult<String> {
+    if data.is_empty() {
+        return Err(VizError::InvalidData("histogram data must not be empty".to_string()));
+    }
+    let mut sorted = data.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let n = sorted.len();
+
+    let iqr = crate::percentile(&sorted, 75.0) - crate::percentile(&sorted, 25.0);
+    let bin_width = 2.0 * iqr * (n as f64).powf(-1.0 / 3.0);
+    let bins = if bin_width > 0.0 {
+        let range = sorted[n - 1] - sorted[0];
+        ((range / bin_width).ceil() as usize).max(1)
+    } else {
+        (n as f64).log2().ceil() as usize + 1
+    };
+
+    term_histogram(data, bins, config)
+}
+
+/// The block characters [`sparkline`] maps values through, lowest to
+/// highest.
+const SPARKLINE_LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Sentinel [`sparkline`] renders for a non-finite (`NaN`, `+-inf`) value,
+/// rather than letting it propagate into the scaling math.
+const SPARKLINE_NAN: char = '·';
+
+/// Renders `values` as one block character per value, scaled between the
+/// series' (finite) min and max. A value equal to the min renders as the
+/// lowest level and a value equal to the max as the highest, so both
+/// extremes stay representable regardless of series length. A series with
+/// no spread (or a single value) renders every value as the middle level.
+/// Non-finite values render as [`SPARKLINE_NAN`] rather than being dropped,
+/// so the output has exactly one character per input value.
+pub fn sparkline(values: &[f64]) -> String {
+    let (Some(low), Some(high)) = (
+        values.iter().copied().filter(|v| v.is_finite()).reduce(f64::min),
+        values.iter().copied().filter(|v| v.is_finite()).reduce(f64::max),
+    ) else {
+        return SPARKLINE_NAN.to_string().repeat(values.len());
+    };
+
+    values
+        .iter()
+        .map(|&v| {
+            if !v.is_finite() {
+                return SPARKLINE_NAN;
+            }
+            if low == high {
+                return SPARKLINE_LEVELS[(SPARKLINE_LEVELS.len() - 1) / 2];
+            }
+            let normalized = ((v - low) / (high - low)).clamp(0.0, 1.0);
+            let idx = (normalized * (SPARKLINE_LEVELS.len() - 1) as f64).round() as usize;
+            SPARKLINE_LEVELS[idx.min(SPARKLINE_LEVELS.len() - 1)]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn an_explicit_size_overrides_auto_detection() {
+        let config = TermPlotConfig { width: 42, height: 7, ..Default::default() };
+        assert_eq!(resolve_dimensions(&config), (42, 7));
+    }
+
+    #[test]
+    fn a_zero_size_falls_back_to_the_default_off_a_tty() {
+        // Test runs are never attached to a real terminal, so auto-detection
+        // always falls through to the documented 80x20 default here.
+        let config = TermPlotConfig::default();
+        assert_eq!(resolve_dimensions(&config), (DEFAULT_WIDTH, DEFAULT_HEIGHT));
+    }
+
+    #[test]
+    fn term_line_plot_renders_a_non_empty_chart() {
+        let points = [(0.0, 0.0), (1.0, 1.0), (2.0, 4.0)];
+        let rendered = term_line_plot(&points, &TermPlotConfig::default());
+        assert!(!rendered.is_empty());
+    }
+
+    #[test]
+    fn term_multi_line_plot_includes_every_series_name_in_the_legend() {
+        let series = vec![
+            ("a".to_string(), vec![(0.0, 0.0), (1.0, 1.0)]),
+            ("b".to_string(), vec![(0.0, 1.0), (1.0, 0.0)]),
+        ];
+        let rendered = term_multi_line_plot(&series, &TermPlotConfig { width: 40, height: 10, ..Default::default() }).unwrap();
+        assert!(rendered.contains('a') && rendered.contains('b'));
+    }
+
+    #[test]
+    fn term_multi_line_plot_rejects_no_series() {
+        let err = term_multi_line_plot(&[], &TermPlotConfig::default()).unwrap_err();
+        assert!(matches!(err, VizError::InvalidData(_)));
+    }
+
+    #[test]
+    fn shuffled_x_values_still_span_the_correct_range() {
+        let sorted_points = [(0.0, 0.0), (1.0, 1.0), (2.0, 4.0), (3.0, 9.0)];
+        let mut shuffled = sorted_points;
+        shuffled.swap(0, 3);
+        shuffled.swap(1, 2);
+
+        let from_sorted = term_line_plot(&sorted_points, &TermPlotConfig { width: 40, height: 10, ..Default::default() });
+        let from_shuffled = term_line_plot(&shuffled, &TermPlotConfig { width: 40, height: 10, ..Default::default() });
+        assert_eq!(from_sorted, from_shuffled);
+    }
+
+    #[test]
+    fn ansi_color_wraps_output_and_respects_no_color() {
+        // Kept as one test (rather than separate present/absent cases) since
+        // NO_COLOR is a process-wide env var and tests run concurrently.
+        std::env::remove_var("NO_COLOR");
+        let colored = TermPlotConfig { width: 40, height: 10, ansi_color: Some((255, 0, 0)) };
+        let plain = TermPlotConfig { width: 40, height: 10, ansi_color: None };
+
+        assert!(term_line_plot(&[(0.0, 0.0), (1.0, 1.0)], &colored).contains("\x1b[38;2;255;0;0m"));
+        assert!(!term_line_plot(&[(0.0, 0.0), (1.0, 1.0)], &plain).contains("\x1b["));
+        assert!(!term_line_plot(&[(0.0, 0.0), (1.0, 1.0)], &colored.no_color()).contains("\x1b["));
+
+        std::env::set_var("NO_COLOR", "1");
+        assert!(!term_line_plot(&[(0.0, 0.0), (1.0, 1.0)], &colored).contains("\x1b["));
+        std::env::remove_var("NO_COLOR");
+    }
+
+    #[test]
+    fn term_box_plot_renders_whisker_glyphs_per_group() {
+        let groups = vec![("a".to_string(), (1..=9).map(|i| i as f64).collect())];
+        let rendered = term_box_plot(&groups, &TermPlotConfig { width: 40, height: 5, ..Default::default() }).unwrap();
+        assert!(rendered.contains('├') && rendered.contains('┤'), "missing whisker ends:\n{rendered}");
+        assert!(rendered.contains('[') && rendered.contains(']'), "missing box edges:\n{rendered}");
+        assert!(rendered.contains('|'), "missing median marker:\n{rendered}");
+    }
+
+    #[test]
+    fn term_box_plot_rejects_an_empty_group() {
+        let groups = vec![("empty".to_string(), vec![])];
+        let err = term_box_plot(&groups, &TermPlotConfig::default()).unwrap_err();
+        assert_eq!(err, crate::VizError::EmptyGroup("empty".to_string()));
+    }
+
+    #[test]
+    fn term_heatmap_spans_the_full_shade_range() {
+        let data = vec![vec![0.0, 1.0, 2.0, 3.0, 4.0]];
+        let rendered = term_heatmap(&data, &TermPlotConfig { width: 5, height: 1, ..Default::default() }).unwrap();
+        assert!(rendered.contains(' '), "missing lightest shade for the minimum:\n{rendered}");
+        assert!(rendered.contains('█'), "missing darkest shade for the maximum:\n{rendered}");
+    }
+
+    #[test]
+    fn term_histogram_auto_splits_a_bimodal_dataset_into_more_than_one_bin() {
+        let mut data: Vec<f64> = (0..50).map(|i| i as f64 * 0.01).collect();
+        data.extend((0..50).map(|i| 10.0 + i as f64 * 0.01));
+        let rendered = term_histogram_auto(&data, &TermPlotConfig::default()).unwrap();
+        assert!(rendered.lines().count() > 1, "expected more than one bin:\n{rendered}");
+    }
+
+    #[test]
+    fn term_histogram_auto_does_not_panic_on_constant_data() {
+        let data = vec![3.0; 20];
+        let rendered = term_histogram_auto(&data, &TermPlotConfig::default()).unwrap();
+        assert_eq!(rendered.lines().count(), 1);
+    }
+
+    #[test]
+    fn term_histogram_rejects_empty_data() {
+        let err = term_histogram(&[], 5, &TermPlotConfig::default()).unwrap_err();
+        assert!(matches!(err, VizError::InvalidData(_)));
+    }
+
+    #[test]
+    fn sparkline_of_all_equal_data_renders_one_char_per_value_without_panicking() {
+        let rendered = sparkline(&[5.0; 5]);
+        assert_eq!(rendered, "▄".repeat(5));
+    }
+
+    #[test]
+    fn sparkline_maps_a_nan_in_the_middle_to_the_sentinel_char() {
+        let rendered = sparkline(&[1.0, f64::NAN, 3.0]);
+        let chars: Vec<char> = rendered.chars().collect();
+        assert_eq!(chars.len(), 3);
+        assert_eq!(chars[1], '·');
+        assert_ne!(chars[0], '·');
+        assert_ne!(chars[2], '·');
+    }
+
+    #[test]
+    fn sparkline_of_a_two_element_series_spans_both_extremes() {
+        let rendered = sparkline(&[1.0, 2.0]);
+        let chars: Vec<char> = rendered.chars().collect();
+        assert_eq!(chars, vec!['▁', '█']);
+    }
+
+    #[test]
+    fn term_heatmap_rejects_ragged_rows() {
+        let data = vec![vec![1.0, 2.0], vec![3.0]];
+        let err = term_heatmap(&data, &TermPlotConfig::default()).unwrap_err();
+        assert!(matches!(err, crate::VizError::InvalidData(_)));
+    }
+}
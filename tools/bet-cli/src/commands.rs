@@ -0,0 +1,500 @@
+//! Implementations of the `bet` subcommands.
+
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use bet_check::TypeEnv;
+use bet_core::{CompileError, CompileResult, Expr};
+use bet_eval::{eval, EvalContext, Value};
+use bet_rt::random::{bernoulli, normal, uniform, Distribution};
+
+use crate::parse::{self, Stmt};
+
+/// How `bet run` should print its final value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// The value's `Display` form (the default).
+    Human,
+    /// Serialized as JSON, for scripting and CI.
+    Json,
+}
+
+/// The terminal width `run`'s human-readable output wraps to, or a sane
+/// default when the output isn't a terminal (e.g. piped to a file).
+const DEFAULT_TERMINAL_WIDTH: usize = 80;
+
+fn terminal_width() -> usize {
+    terminal_size::terminal_size().map_or(DEFAULT_TERMINAL_WIDTH, |(w, _)| w.0 as usize)
+}
+
+/// Type-checks, then runs the betlang program in `path` and prints its
+/// final value.
+pub fn run(path: &Path, output: OutputFormat) -> CompileResult<()> {
+    let expr = parse_file(path)?;
+    tracing::info!("parsed 1 expression");
+    bet_check::check(&expr, &TypeEnv::new())?;
+    let value = eval(&expr, &bet_eval::prelude(), &mut EvalContext::default())?;
+    match output {
+        OutputFormat::Human => println!("{}", bet_rt::pretty::pretty_print(&value, terminal_width())),
+        OutputFormat::Json => {
+            let json = value_to_json(&value)?;
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&json).expect("serde_json::Value always serializes")
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Type-checks the betlang program in `path` and prints its inferred type.
+pub fn check(path: &Path) -> CompileResult<()> {
+    let expr = parse_file(path)?;
+    let ty = bet_check::check(&expr, &TypeEnv::new())?;
+    println!("{ty}");
+    Ok(())
+}
+
+/// The sparkline characters used to sketch a distribution's density, from
+/// lowest to highest relative frequency.
+const SKETCH_CHARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// An analytic (and, for the density sketch, sampled) summary of a
+/// distribution, as reported by `bet dist`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DistSummary {
+    pub family: String,
+    pub mean: Option<f64>,
+    pub variance: Option<f64>,
+    pub support: Option<(f64, f64)>,
+    pub sketch: String,
+}
+
+impl fmt::Display for DistSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.family)?;
+        match self.mean {
+            Some(mean) => writeln!(f, "  mean:     {mean}")?,
+            None => writeln!(f, "  mean:     (unknown)")?,
+        }
+        match self.variance {
+            Some(variance) => writeln!(f, "  variance: {variance}")?,
+            None => writeln!(f, "  variance: (unknown)")?,
+        }
+        match self.support {
+            Some((low, high)) => writeln!(f, "  support:  [{low}, {high}]")?,
+            None => writeln!(f, "  support:  (unknown)")?,
+        }
+        write!(f, "  density:  {}", self.sketch)
+    }
+}
+
+/// Parses a distribution description like `"normal 0 1"` into a
+/// [`Distribution`], shared by `bet dist` and `bet sample`.
+fn parse_dist_spec(spec: &str) -> CompileResult<std::sync::Arc<Distribution>> {
+    let mut tokens = spec.split_whitespace();
+    let family = tokens
+        .next()
+        .ok_or_else(|| CompileError::Parse("expected a distribution family name".into()))?;
+    let params = tokens
+        .map(|t| t.parse::<f64>().map_err(|_| CompileError::Parse(format!("`{t}` is not a number"))))
+        .collect::<CompileResult<Vec<f64>>>()?;
+
+    let value = match (family, &params[..]) {
+        ("uniform", [low, high]) => uniform(*low, *high),
+        ("normal", [mean, std]) => normal(*mean, *std),
+        ("bernoulli", [p]) => bernoulli(*p),
+        (other, _) => {
+            return Err(CompileError::Runtime(format!(
+                "unknown distribution `{other}` or the wrong number of parameters for it"
+            )))
+        }
+    }
+    .map_err(CompileError::Runtime)?;
+    let Value::Dist(dist) = value else {
+        unreachable!("random::{{uniform,normal,bernoulli}} always build a Dist")
+    };
+    Ok(dist)
+}
+
+/// Describes the distribution named by `spec` (e.g. `"normal 0 1"`): its
+/// analytic mean, variance, and support where the family has a closed
+/// form, plus a sampled terminal density sketch so users can sanity-check
+/// a parameterization at a glance.
+pub fn describe_dist(spec: &str) -> CompileResult<DistSummary> {
+    let dist = parse_dist_spec(spec)?;
+    let family = spec.split_whitespace().next().unwrap_or_default().to_string();
+
+    Ok(DistSummary {
+        family,
+        mean: dist.mean(),
+        variance: dist.variance(),
+        support: dist.support(),
+        sketch: sketch_density(&dist),
+    })
+}
+
+const SKETCH_BUCKETS: usize = 20;
+
+/// Buckets `draws` into a sparkline, one character per bucket, scaled to the
+/// busiest bucket.
+fn sketch_from_draws(draws: &[f64]) -> String {
+    let (Some(low), Some(high)) = (
+        draws.iter().cloned().reduce(f64::min),
+        draws.iter().cloned().reduce(f64::max),
+    ) else {
+        return String::new();
+    };
+    if low == high {
+        return SKETCH_CHARS[SKETCH_CHARS.len() - 1].to_string().repeat(SKETCH_BUCKETS);
+    }
+
+    let mut counts = [0usize; SKETCH_BUCKETS];
+    for x in draws {
+        let bucket = (((x - low) / (high - low)) * SKETCH_BUCKETS as f64).floor() as usize;
+        counts[bucket.min(SKETCH_BUCKETS - 1)] += 1;
+    }
+    let max = *counts.iter().max().unwrap_or(&1);
+    counts
+        .iter()
+        .map(|&c| SKETCH_CHARS[(c * (SKETCH_CHARS.len() - 1) / max.max(1)).min(SKETCH_CHARS.len() - 1)])
+        .collect()
+}
+
+/// Draws 1000 samples and buckets them into a sparkline, one character per
+/// bucket, scaled to the busiest bucket.
+fn sketch_density(dist: &Distribution) -> String {
+    const SAMPLES: usize = 1000;
+    let draws: Vec<f64> = (0..SAMPLES).filter_map(|_| value_as_float(&dist.sample())).collect();
+    sketch_from_draws(&draws)
+}
+
+/// Coerces a sampled [`Value`] to `f64` for statistics, or `None` for
+/// families (like `Value::Unit`) that don't sample to a number.
+fn value_as_float(value: &Value) -> Option<f64> {
+    match value {
+        Value::Float(x) => Some(*x),
+        Value::Int(i) => Some(*i as f64),
+        Value::Bool(b) => Some(if *b { 1.0 } else { 0.0 }),
+        _ => None,
+    }
+}
+
+/// Prints [`describe_dist`]'s summary of the distribution named by `spec`.
+pub fn dist(spec: &str) -> CompileResult<()> {
+    println!("{}", describe_dist(spec)?);
+    Ok(())
+}
+
+/// How many draws `sample` pulls before reporting a progress update.
+const SAMPLE_BATCH_SIZE: usize = 100;
+
+/// Draws `n` samples from the distribution named by `spec` in batches,
+/// printing a running mean/std after each batch rather than waiting for
+/// all `n` to finish, then finalizing with a density sketch over every
+/// draw collected.
+pub fn sample(spec: &str, n: usize) -> CompileResult<()> {
+    let dist = parse_dist_spec(spec)?;
+    let mut stats = bet_rt::stats::OnlineStats::new();
+    let mut draws = Vec::with_capacity(n);
+
+    let mut drawn = 0;
+    while drawn < n {
+        let batch = SAMPLE_BATCH_SIZE.min(n - drawn);
+        for _ in 0..batch {
+            let value = dist.sample();
+            if let Some(x) = value_as_float(&value) {
+                stats.update(x);
+                draws.push(x);
+            }
+        }
+        drawn += batch;
+        println!(
+            "{drawn}/{n}  mean={:.4}  std={:.4}",
+            stats.mean().unwrap_or(f64::NAN),
+            stats.std().unwrap_or(f64::NAN)
+        );
+    }
+
+    println!("final mean: {:.4}", stats.mean().unwrap_or(f64::NAN));
+    println!("final std:  {:.4}", stats.std().unwrap_or(f64::NAN));
+    println!("density:    {}", sketch_from_draws(&draws));
+    Ok(())
+}
+
+/// The result of timing how fast a distribution can be sampled.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BenchResult {
+    pub samples_per_sec: f64,
+}
+
+/// Draws `n` samples from the distribution named by `spec`, timing the
+/// whole run, and reports the throughput.
+pub fn bench(spec: &str, n: usize) -> CompileResult<BenchResult> {
+    let dist = parse_dist_spec(spec)?;
+    let start = std::time::Instant::now();
+    for _ in 0..n {
+        dist.sample();
+    }
+    let elapsed = start.elapsed().as_secs_f64();
+    let samples_per_sec = if elapsed > 0.0 { n as f64 / elapsed } else { f64::INFINITY };
+    Ok(BenchResult { samples_per_sec })
+}
+
+/// Writes `result` to `path` as JSON, for a later `bet bench --baseline` run
+/// to compare against.
+fn save_baseline(path: &Path, result: &BenchResult) -> CompileResult<()> {
+    let json = serde_json::json!({ "samples_per_sec": result.samples_per_sec });
+    let text = serde_json::to_string_pretty(&json).expect("serde_json::Value always serializes");
+    fs::write(path, text).map_err(|e| CompileError::Io(format!("{}: {e}", path.display())))
+}
+
+/// Reads a baseline's `samples_per_sec` back out of the JSON file `save_baseline` wrote.
+fn load_baseline(path: &Path) -> CompileResult<f64> {
+    let text = fs::read_to_string(path).map_err(|e| CompileError::Io(format!("{}: {e}", path.display())))?;
+    let json: serde_json::Value =
+        serde_json::from_str(&text).map_err(|e| CompileError::Io(format!("{}: {e}", path.display())))?;
+    json.get("samples_per_sec")
+        .and_then(serde_json::Value::as_f64)
+        .ok_or_else(|| CompileError::Io(format!("{}: missing `samples_per_sec`", path.display())))
+}
+
+/// Whether `current` throughput is more than `threshold` (a fraction, e.g.
+/// `0.1` for 10%) below `baseline` — a performance regression worth failing
+/// CI over.
+fn throughput_regressed(baseline: f64, current: f64, threshold: f64) -> bool {
+    current < baseline * (1.0 - threshold)
+}
+
+/// `bet bench`: times sampling `spec` `n` times, optionally saving the
+/// result as a baseline (`save_baseline`) and/or comparing against a
+/// previously saved one (`baseline`), failing if throughput regressed by
+/// more than `threshold`.
+pub fn run_bench(
+    spec: &str,
+    n: usize,
+    baseline: Option<&Path>,
+    save_baseline_to: Option<&Path>,
+    threshold: f64,
+) -> CompileResult<()> {
+    let result = bench(spec, n)?;
+    println!("samples/sec: {:.1}", result.samples_per_sec);
+
+    if let Some(path) = save_baseline_to {
+        save_baseline(path, &result)?;
+        println!("saved baseline to {}", path.display());
+    }
+
+    if let Some(path) = baseline {
+        let baseline_rate = load_baseline(path)?;
+        println!("baseline samples/sec: {baseline_rate:.1}");
+        if throughput_regressed(baseline_rate, result.samples_per_sec, threshold) {
+            return Err(CompileError::Runtime(format!(
+                "throughput regressed: {:.1} samples/sec is more than {:.0}% below the baseline {:.1}",
+                result.samples_per_sec,
+                threshold * 100.0,
+                baseline_rate
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+/// A leading UTF-8 byte order mark, as some editors prepend to text files.
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+
+fn parse_file(path: &Path) -> CompileResult<Expr> {
+    let bytes = fs::read(path).map_err(|e| CompileError::Io(format!("{}: {e}", path.display())))?;
+    let bytes = bytes.strip_prefix(UTF8_BOM).unwrap_or(&bytes);
+    let source = String::from_utf8(bytes.to_vec())
+        .map_err(|_| CompileError::Io(format!("{}: file is not valid UTF-8", path.display())))?;
+    let stmt = parse::parse_line(source.trim()).map_err(CompileError::Parse)?;
+    Ok(match stmt {
+        Stmt::Expr(expr) => expr,
+        Stmt::Let(_, expr) => expr,
+    })
+}
+
+/// Converts a [`Value`] to JSON, erroring clearly on values with no JSON
+/// representation (closures).
+fn value_to_json(value: &Value) -> CompileResult<serde_json::Value> {
+    match value {
+        Value::Unit => Ok(serde_json::Value::Null),
+        Value::Bool(b) => Ok(serde_json::Value::Bool(*b)),
+        Value::Ternary(t) => Ok(serde_json::Value::String(format!("{t:?}"))),
+        Value::Int(i) => Ok(serde_json::Value::Number((*i).into())),
+        Value::Float(x) => serde_json::Number::from_f64(*x)
+            .map(serde_json::Value::Number)
+            .ok_or_else(|| CompileError::Runtime(format!("{x} has no JSON representation"))),
+        Value::String(s) => Ok(serde_json::Value::String(s.clone())),
+        Value::List(items) => items
+            .iter()
+            .map(value_to_json)
+            .collect::<CompileResult<Vec<_>>>()
+            .map(serde_json::Value::Array),
+        Value::Tuple(items) => items
+            .iter()
+            .map(value_to_json)
+            .collect::<CompileResult<Vec<_>>>()
+            .map(serde_json::Value::Array),
+        Value::Closure(_) => Err(CompileError::Runtime("closures cannot be serialized to JSON".into())),
+        other => Err(CompileError::Runtime(format!("`{other}` has no JSON representation"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bet_core::{BinOp, Literal};
+    use bet_eval::ValueEnv;
+
+    use super::*;
+
+    fn eval_source(source: &str) -> CompileResult<Value> {
+        let stmt = parse::parse_line(source.trim()).map_err(CompileError::Parse)?;
+        let expr = match stmt {
+            Stmt::Expr(expr) => expr,
+            Stmt::Let(_, expr) => expr,
+        };
+        eval(&expr, &ValueEnv::new(), &mut EvalContext::default())
+    }
+
+    #[test]
+    fn eval_source_reports_a_computed_int() {
+        assert_eq!(eval_source("1 + 1"), Ok(Value::Int(2)));
+    }
+
+    #[test]
+    fn json_output_of_a_list_round_trips() {
+        let value = Value::List(im::vector![Value::Int(1), Value::Int(2), Value::Int(3)]);
+        let json = value_to_json(&value).unwrap();
+        assert_eq!(json, serde_json::json!([1, 2, 3]));
+    }
+
+    #[test]
+    fn closures_cannot_be_serialized_to_json() {
+        let closure = bet_eval::Closure {
+            param: "x".into(),
+            body: bet_core::Expr::Var("x".into()),
+            env: ValueEnv::new(),
+        };
+        assert!(value_to_json(&Value::Closure(std::sync::Arc::new(closure))).is_err());
+    }
+
+    #[test]
+    fn a_parse_failure_reports_a_parse_error() {
+        assert!(matches!(eval_source("1 +"), Err(CompileError::Parse(_))));
+    }
+
+    #[test]
+    fn run_rejects_a_file_that_fails_to_type_check() {
+        let file = write_bytes(b"1 + 1.5");
+        assert!(matches!(run(file.path(), OutputFormat::Human), Err(CompileError::UnificationError(_))));
+    }
+
+    #[test]
+    fn uniform_dist_reports_its_analytic_mean_and_variance() {
+        let summary = describe_dist("uniform 0 1").unwrap();
+        assert_eq!(summary.mean, Some(0.5));
+        assert_eq!(summary.variance, Some(1.0 / 12.0));
+        assert_eq!(summary.support, Some((0.0, 1.0)));
+    }
+
+    #[test]
+    fn unknown_dist_family_is_a_runtime_error() {
+        assert!(matches!(describe_dist("not_a_dist 1 2"), Err(CompileError::Runtime(_))));
+    }
+
+    #[test]
+    fn a_missing_dist_name_is_a_parse_error() {
+        assert!(matches!(describe_dist(""), Err(CompileError::Parse(_))));
+    }
+
+    #[test]
+    fn sampling_in_batches_matches_a_single_batch_for_the_same_draws() {
+        // This doesn't compare two live `bet sample` runs against each
+        // other; instead it checks
+        // that OnlineStats itself, which is what `sample` streams through,
+        // reports the same final mean whether it's fed one draw at a time
+        // or in `SAMPLE_BATCH_SIZE`-sized batches.
+        let draws: Vec<f64> = (0..(SAMPLE_BATCH_SIZE * 3 + 17)).map(|i| i as f64).collect();
+
+        let mut one_at_a_time = bet_rt::stats::OnlineStats::new();
+        for &x in &draws {
+            one_at_a_time.update(x);
+        }
+
+        let mut batched = bet_rt::stats::OnlineStats::new();
+        for batch in draws.chunks(SAMPLE_BATCH_SIZE) {
+            for &x in batch {
+                batched.update(x);
+            }
+        }
+
+        assert_eq!(batched.count(), one_at_a_time.count());
+        assert!((batched.mean().unwrap() - one_at_a_time.mean().unwrap()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn sample_reports_a_final_summary_for_a_known_distribution() {
+        assert!(sample("bernoulli 0.5", 50).is_ok());
+    }
+
+    fn write_bytes(contents: &[u8]) -> tempfile::NamedTempFile {
+        use std::io::Write;
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(contents).unwrap();
+        file
+    }
+
+    #[test]
+    fn a_leading_bom_is_stripped_before_parsing() {
+        let mut bytes = UTF8_BOM.to_vec();
+        bytes.extend_from_slice(b"1 + 1");
+        let file = write_bytes(&bytes);
+        let expected = Expr::BinOp(
+            BinOp::Add,
+            Box::new(Expr::Literal(Literal::Int(1))),
+            Box::new(Expr::Literal(Literal::Int(1))),
+        );
+        assert_eq!(parse_file(file.path()), Ok(expected));
+    }
+
+    #[test]
+    fn non_utf8_source_reports_a_clear_io_error() {
+        let file = write_bytes(&[0xFF, 0xFE, 0xFD]);
+        assert!(matches!(parse_file(file.path()), Err(CompileError::Io(_))));
+    }
+
+    #[test]
+    fn a_large_drop_in_throughput_is_a_regression() {
+        // 20% slower than baseline, threshold is 10%.
+        assert!(throughput_regressed(100.0, 80.0, 0.1));
+    }
+
+    #[test]
+    fn a_small_drop_in_throughput_is_not_a_regression() {
+        // 5% slower than baseline, threshold is 10%.
+        assert!(!throughput_regressed(100.0, 95.0, 0.1));
+    }
+
+    #[test]
+    fn faster_than_baseline_is_not_a_regression() {
+        assert!(!throughput_regressed(100.0, 150.0, 0.1));
+    }
+
+    #[test]
+    fn a_saved_baseline_round_trips_through_load() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let result = BenchResult { samples_per_sec: 12345.6 };
+        save_baseline(file.path(), &result).unwrap();
+        assert_eq!(load_baseline(file.path()).unwrap(), result.samples_per_sec);
+    }
+
+    #[test]
+    fn bench_reports_a_positive_throughput_for_a_known_distribution() {
+        let result = bench("bernoulli 0.5", 100).unwrap();
+        assert!(result.samples_per_sec > 0.0);
+    }
+}
@@ -0,0 +1,109 @@
+//! `bet`: the betlang command-line interface.
+
+mod commands;
+mod parse;
+mod repl;
+
+use std::path::PathBuf;
+
+use bet_core::CompileError;
+use clap::{Parser, Subcommand};
+use commands::OutputFormat;
+use repl::ReplConfig;
+
+#[derive(Parser)]
+#[command(name = "bet")]
+struct Cli {
+    /// Suppress non-essential output (e.g. the "parsed 1 expression" line).
+    #[arg(long, global = true)]
+    quiet: bool,
+    /// Raise the tracing filter to DEBUG.
+    #[arg(long, global = true)]
+    verbose: bool,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+/// Sets up the `tracing` subscriber that [`commands::run`]'s progress
+/// messages go through. `--verbose` wins over `--quiet` if both are given.
+fn init_tracing(quiet: bool, verbose: bool) {
+    let level = if verbose {
+        "debug"
+    } else if quiet {
+        "warn"
+    } else {
+        "info"
+    };
+    tracing_subscriber::fmt()
+        .with_env_filter(tracing_subscriber::EnvFilter::new(level))
+        .without_time()
+        .with_target(false)
+        .init();
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Start the interactive REPL.
+    Repl,
+    /// Run a betlang source file.
+    Run {
+        file: PathBuf,
+        #[arg(long, value_enum, default_value = "human")]
+        output: OutputFormat,
+    },
+    /// Type-check a betlang source file without running it.
+    Check { file: PathBuf },
+    /// Describe a distribution's analytic mean, variance, and support.
+    Dist {
+        /// A distribution description, e.g. `"normal 0 1"`.
+        expr: String,
+    },
+    /// Draw samples from a distribution, streaming a running mean/std.
+    Sample {
+        /// A distribution description, e.g. `"normal 0 1"`.
+        expr: String,
+        /// How many samples to draw.
+        #[arg(long, default_value_t = 1000)]
+        n: usize,
+    },
+    /// Time how fast a distribution can be sampled, optionally comparing
+    /// against a saved baseline to catch performance regressions.
+    Bench {
+        /// A distribution description, e.g. `"normal 0 1"`.
+        expr: String,
+        /// How many samples to draw while timing.
+        #[arg(long, default_value_t = 10_000)]
+        n: usize,
+        /// A previously saved baseline to compare throughput against.
+        #[arg(long)]
+        baseline: Option<PathBuf>,
+        /// Save this run's throughput to a baseline file.
+        #[arg(long)]
+        save_baseline: Option<PathBuf>,
+        /// Fail if throughput drops below the baseline by more than this
+        /// fraction (default 10%).
+        #[arg(long, default_value_t = 0.1)]
+        threshold: f64,
+    },
+}
+
+fn main() {
+    let cli = Cli::parse();
+    init_tracing(cli.quiet, cli.verbose);
+    let result = match cli.command {
+        None | Some(Command::Repl) => {
+            repl::run_repl(ReplConfig::default()).map_err(|e| CompileError::Io(e.to_string()))
+        }
+        Some(Command::Run { file, output }) => commands::run(&file, output),
+        Some(Command::Check { file }) => commands::check(&file),
+        Some(Command::Dist { expr }) => commands::dist(&expr),
+        Some(Command::Sample { expr, n }) => commands::sample(&expr, n),
+        Some(Command::Bench { expr, n, baseline, save_baseline, threshold }) => {
+            commands::run_bench(&expr, n, baseline.as_deref(), save_baseline.as_deref(), threshold)
+        }
+    };
+    if let Err(e) = result {
+        eprintln!("error: {e}");
+        std::process::exit(e.exit_code());
+    }
+}
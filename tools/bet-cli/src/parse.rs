@@ -0,0 +1,191 @@
+//! A small expression grammar for the REPL: integer/float arithmetic,
+//! parentheses, variables, and `let` bindings. This is deliberately far
+//! short of full betlang syntax — just enough to drive [`crate::repl`]
+//! until betlang has a real parser of its own.
+
+use bet_core::{BinOp, Expr, Literal, UnOp};
+
+/// A single REPL line: either a binding or a bare expression to evaluate.
+pub enum Stmt {
+    Let(String, Expr),
+    Expr(Expr),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Let,
+    Ident(String),
+    Int(i64),
+    Float(f64),
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Eq,
+    LParen,
+    RParen,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let chars: Vec<char> = input.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            ' ' | '\t' | '\n' | '\r' => i += 1,
+            '+' => {
+                tokens.push(Token::Plus);
+                i += 1;
+            }
+            '-' => {
+                tokens.push(Token::Minus);
+                i += 1;
+            }
+            '*' => {
+                tokens.push(Token::Star);
+                i += 1;
+            }
+            '/' => {
+                tokens.push(Token::Slash);
+                i += 1;
+            }
+            '=' => {
+                tokens.push(Token::Eq);
+                i += 1;
+            }
+            '(' => {
+                tokens.push(Token::LParen);
+                i += 1;
+            }
+            ')' => {
+                tokens.push(Token::RParen);
+                i += 1;
+            }
+            c if c.is_ascii_digit() => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                if text.contains('.') {
+                    let x = text.parse().map_err(|_| format!("invalid number `{text}`"))?;
+                    tokens.push(Token::Float(x));
+                } else {
+                    let n = text.parse().map_err(|_| format!("invalid number `{text}`"))?;
+                    tokens.push(Token::Int(n));
+                }
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                let text: String = chars[start..i].iter().collect();
+                tokens.push(if text == "let" { Token::Let } else { Token::Ident(text) });
+            }
+            other => return Err(format!("unexpected character `{other}`")),
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn expect(&mut self, token: &Token) -> Result<(), String> {
+        match self.next() {
+            Some(t) if t == *token => Ok(()),
+            other => Err(format!("expected {token:?}, found {other:?}")),
+        }
+    }
+
+    fn parse_stmt(&mut self) -> Result<Stmt, String> {
+        if self.peek() == Some(&Token::Let) {
+            self.next();
+            let name = match self.next() {
+                Some(Token::Ident(name)) => name,
+                other => return Err(format!("expected an identifier after `let`, found {other:?}")),
+            };
+            self.expect(&Token::Eq)?;
+            let value = self.parse_expr()?;
+            return Ok(Stmt::Let(name, value));
+        }
+        Ok(Stmt::Expr(self.parse_expr()?))
+    }
+
+    fn parse_expr(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.parse_term()?;
+        loop {
+            match self.peek() {
+                Some(Token::Plus) => {
+                    self.next();
+                    lhs = Expr::BinOp(BinOp::Add, Box::new(lhs), Box::new(self.parse_term()?));
+                }
+                Some(Token::Minus) => {
+                    self.next();
+                    lhs = Expr::BinOp(BinOp::Sub, Box::new(lhs), Box::new(self.parse_term()?));
+                }
+                _ => break,
+            }
+        }
+        Ok(lhs)
+    }
+
+    fn parse_term(&mut self) -> Result<Expr, String> {
+        let mut lhs = self.parse_factor()?;
+        loop {
+            match self.peek() {
+                Some(Token::Star) => {
+                    self.next();
+                    lhs = Expr::BinOp(BinOp::Mul, Box::new(lhs), Box::new(self.parse_factor()?));
+                }
+                Some(Token::Slash) => {
+                    self.next();
+                    lhs = Expr::BinOp(BinOp::Div, Box::new(lhs), Box::new(self.parse_factor()?));
+                }
+                _ => break,
+            }
+        }
+        Ok(lhs)
+    }
+
+    fn parse_factor(&mut self) -> Result<Expr, String> {
+        match self.next() {
+            Some(Token::Int(n)) => Ok(Expr::Literal(Literal::Int(n))),
+            Some(Token::Float(x)) => Ok(Expr::Literal(Literal::Float(x))),
+            Some(Token::Ident(name)) => Ok(Expr::Var(name)),
+            Some(Token::Minus) => Ok(Expr::UnOp(UnOp::Neg, Box::new(self.parse_factor()?))),
+            Some(Token::LParen) => {
+                let inner = self.parse_expr()?;
+                self.expect(&Token::RParen)?;
+                Ok(inner)
+            }
+            other => Err(format!("expected a number, variable, or `(`, found {other:?}")),
+        }
+    }
+}
+
+/// Parses one REPL line into a [`Stmt`].
+pub fn parse_line(input: &str) -> Result<Stmt, String> {
+    let tokens = tokenize(input)?;
+    let mut parser = Parser { tokens, pos: 0 };
+    let stmt = parser.parse_stmt()?;
+    if parser.pos != parser.tokens.len() {
+        return Err("unexpected trailing input".to_string());
+    }
+    Ok(stmt)
+}
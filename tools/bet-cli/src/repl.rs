@@ -0,0 +1,181 @@
+//! The interactive read-eval-print loop.
+
+use std::path::PathBuf;
+
+use bet_eval::{eval, prelude, EvalContext, ValueEnv};
+use rustyline::error::ReadlineError;
+use rustyline::DefaultEditor;
+
+use crate::parse::{self, Stmt};
+
+/// Settings for [`run_repl`], so embedders and tests can run it
+/// non-interactively or with a custom prompt instead of the hardcoded
+/// defaults.
+pub struct ReplConfig {
+    pub prompt: String,
+    pub show_banner: bool,
+    pub history_file: Option<PathBuf>,
+    pub color: bool,
+}
+
+impl Default for ReplConfig {
+    fn default() -> Self {
+        ReplConfig {
+            prompt: "bet> ".to_string(),
+            show_banner: true,
+            history_file: None,
+            color: true,
+        }
+    }
+}
+
+const BANNER: &str = "betlang REPL. Type `:help` for help, or `exit`/`quit` to leave.";
+const HELP_TEXT: &str = "Commands:\n  :help    show this message\n  exit, quit  leave the REPL\nOtherwise, enter an expression (`1 + 2`) or a binding (`let x = 5`).";
+
+/// Evaluation state carried between [`process_line`] calls.
+pub struct ReplState {
+    env: ValueEnv,
+}
+
+impl Default for ReplState {
+    fn default() -> Self {
+        ReplState { env: prelude() }
+    }
+}
+
+/// What a REPL line produced, decoupled from any actual I/O.
+pub enum ReplOutput {
+    /// A line of text to show the user.
+    Message(String),
+    /// The REPL should stop reading further input.
+    Exit,
+}
+
+/// Processes one line of REPL input against `state`, without touching any
+/// I/O. This is what [`run_repl`] drives in a loop over stdin; kept
+/// separate so tests can exercise it directly.
+pub fn process_line(line: &str, state: &mut ReplState) -> ReplOutput {
+    let trimmed = line.trim();
+    if trimmed.is_empty() {
+        return ReplOutput::Message(String::new());
+    }
+    if trimmed == "exit" || trimmed == "quit" {
+        return ReplOutput::Exit;
+    }
+    if trimmed == ":help" {
+        return ReplOutput::Message(HELP_TEXT.to_string());
+    }
+
+    match parse::parse_line(trimmed) {
+        Ok(Stmt::Let(name, expr)) => match eval(&expr, &state.env, &mut EvalContext::default()) {
+            Ok(value) => {
+                state.env.insert(name.clone(), value.clone());
+                ReplOutput::Message(format!("{name} = {value}"))
+            }
+            Err(e) => ReplOutput::Message(format!("error: {e}")),
+        },
+        Ok(Stmt::Expr(expr)) => match eval(&expr, &state.env, &mut EvalContext::default()) {
+            Ok(value) => ReplOutput::Message(value.to_string()),
+            Err(e) => ReplOutput::Message(format!("error: {e}")),
+        },
+        Err(e) => ReplOutput::Message(format!("error: {e}")),
+    }
+}
+
+/// Wraps `text` in ANSI bold when `color` is enabled; otherwise returns it
+/// unchanged.
+fn colorize(text: &str, color: bool) -> String {
+    if color {
+        format!("\x1b[1m{text}\x1b[0m")
+    } else {
+        text.to_string()
+    }
+}
+
+/// Runs the REPL against a `rustyline` editor until the user exits. Thin
+/// I/O wrapper around [`process_line`].
+pub fn run_repl(config: ReplConfig) -> rustyline::Result<()> {
+    let mut editor = DefaultEditor::new()?;
+    if let Some(path) = &config.history_file {
+        let _ = editor.load_history(path);
+    }
+
+    if config.show_banner {
+        println!("{}", colorize(BANNER, config.color));
+    }
+
+    let mut state = ReplState::default();
+    loop {
+        match editor.readline(&colorize(&config.prompt, config.color)) {
+            Ok(line) => {
+                editor.add_history_entry(line.as_str())?;
+                match process_line(&line, &mut state) {
+                    ReplOutput::Message(msg) => {
+                        if !msg.is_empty() {
+                            println!("{msg}");
+                        }
+                    }
+                    ReplOutput::Exit => break,
+                }
+            }
+            Err(ReadlineError::Interrupted) | Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(e),
+        }
+    }
+
+    if let Some(path) = &config.history_file {
+        editor.save_history(path)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn banner_can_be_disabled() {
+        let config = ReplConfig {
+            show_banner: false,
+            ..ReplConfig::default()
+        };
+        assert!(!config.show_banner);
+    }
+
+    #[test]
+    fn help_command_returns_help_text() {
+        let mut state = ReplState::default();
+        match process_line(":help", &mut state) {
+            ReplOutput::Message(msg) => assert!(msg.contains("Commands")),
+            ReplOutput::Exit => panic!("expected a message, not exit"),
+        }
+    }
+
+    #[test]
+    fn a_binding_is_remembered_for_later_expressions() {
+        let mut state = ReplState::default();
+        match process_line("let x = 5", &mut state) {
+            ReplOutput::Message(msg) => assert_eq!(msg, "x = 5"),
+            ReplOutput::Exit => panic!("expected a message, not exit"),
+        }
+        match process_line("x + 1", &mut state) {
+            ReplOutput::Message(msg) => assert_eq!(msg, "6"),
+            ReplOutput::Exit => panic!("expected a message, not exit"),
+        }
+    }
+
+    #[test]
+    fn an_expression_evaluates_to_its_value() {
+        let mut state = ReplState::default();
+        match process_line("2 * (3 + 4)", &mut state) {
+            ReplOutput::Message(msg) => assert_eq!(msg, "14"),
+            ReplOutput::Exit => panic!("expected a message, not exit"),
+        }
+    }
+
+    #[test]
+    fn exit_stops_the_loop() {
+        let mut state = ReplState::default();
+        assert!(matches!(process_line("exit", &mut state), ReplOutput::Exit));
+    }
+}
@@ -0,0 +1,63 @@
+//! Exercises the CLI's exit-code contract end to end: 0 success, 1 runtime
+//! error, 2 type error, 3 parse error, 4 I/O error.
+
+use std::io::Write;
+use std::process::Command;
+
+fn bet() -> Command {
+    Command::new(env!("CARGO_BIN_EXE_bet"))
+}
+
+fn write_source(contents: &str) -> tempfile::NamedTempFile {
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    file.write_all(contents.as_bytes()).unwrap();
+    file
+}
+
+#[test]
+fn run_on_a_valid_file_exits_zero() {
+    let file = write_source("1 + 1");
+    let status = bet().arg("run").arg(file.path()).status().unwrap();
+    assert_eq!(status.code(), Some(0));
+}
+
+#[test]
+fn run_on_division_by_zero_exits_one() {
+    let file = write_source("10 / 0");
+    let status = bet().arg("run").arg(file.path()).status().unwrap();
+    assert_eq!(status.code(), Some(1));
+}
+
+#[test]
+fn check_on_an_unbound_variable_exits_two() {
+    let file = write_source("undefined_name");
+    let status = bet().arg("check").arg(file.path()).status().unwrap();
+    assert_eq!(status.code(), Some(2));
+}
+
+#[test]
+fn run_on_a_type_error_exits_two_without_evaluating() {
+    let file = write_source("1 + 1.5");
+    let status = bet().arg("run").arg(file.path()).status().unwrap();
+    assert_eq!(status.code(), Some(2));
+}
+
+#[test]
+fn run_on_malformed_syntax_exits_three() {
+    let file = write_source("1 +");
+    let status = bet().arg("run").arg(file.path()).status().unwrap();
+    assert_eq!(status.code(), Some(3));
+}
+
+#[test]
+fn run_on_a_missing_file_exits_four() {
+    let status = bet().arg("run").arg("/nonexistent/path/to/nowhere.bet").status().unwrap();
+    assert_eq!(status.code(), Some(4));
+}
+
+#[test]
+fn quiet_run_prints_only_the_result() {
+    let file = write_source("1 + 1");
+    let output = bet().arg("--quiet").arg("run").arg(file.path()).output().unwrap();
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "2\n");
+}